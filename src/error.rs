@@ -4,12 +4,25 @@ use core::alloc::LayoutError;
 use core::fmt;
 
 /// An enumeration of error types raised by storage implementations.
+///
+/// Additional variants may be added in future releases. Use
+/// [`StorageError::is_capacity_limit`] to detect any exhaustion of the
+/// available capacity.
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum StorageError {
     /// A memory allocation failed.
     AllocError,
     /// The limit of the current allocation was reached.
     CapacityLimit,
+    /// The requested capacity could not be represented by the index type
+    /// of the collection.
+    IndexOverflow {
+        /// The requested capacity, saturated at `usize::MAX`.
+        requested: usize,
+        /// The maximum capacity supported by the index type.
+        max: usize,
+    },
     /// The provided layout was not allocatable.
     LayoutError(LayoutError),
     /// The requested operation is not supported for this storage.
@@ -22,22 +35,38 @@ impl StorageError {
         match self {
             Self::AllocError => "Allocation error",
             Self::CapacityLimit => "Exceeded storage capacity limit",
+            Self::IndexOverflow { .. } => "Exceeded index capacity",
             Self::LayoutError(_) => "Layout error",
             Self::Unsupported => "Unsupported",
         }
     }
 
+    /// Determine whether this error indicates that the capacity of the
+    /// storage or index type was exhausted.
+    #[inline]
+    pub fn is_capacity_limit(&self) -> bool {
+        matches!(self, Self::CapacityLimit | Self::IndexOverflow { .. })
+    }
+
     /// Generate a panic with this error as the reason.
     #[cold]
     #[inline(never)]
     pub fn panic(self) -> ! {
-        panic!("{}", self.as_str());
+        panic!("{}", self);
     }
 }
 
 impl fmt::Display for StorageError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.write_str(self.as_str())
+        match self {
+            Self::IndexOverflow { requested, max } => f.write_fmt(format_args!(
+                "{}: requested {}, maximum {}",
+                self.as_str(),
+                requested,
+                max
+            )),
+            _ => f.write_str(self.as_str()),
+        }
     }
 }
 
@@ -83,7 +112,7 @@ impl<T> UpdateError<T> {
     #[cold]
     #[inline(never)]
     pub fn panic(self) -> ! {
-        panic!("{}: {}", self.as_str(), self.error.as_str());
+        panic!("{}: {}", self.as_str(), self.error);
     }
 }
 
@@ -5,20 +5,13 @@ use core::fmt::{Debug, Display};
 use crate::storage::utils::min_non_zero_cap;
 
 /// Types which may be used to index and define the length and capacity of collections.
+///
+/// This trait is implemented for `u8`, `u16`, `u32`, `u64`, and `usize`. For types
+/// which may represent values larger than `usize::MAX` (such as `u64` on 32-bit
+/// targets), [`Index::MAX_USIZE`] is limited to `usize::MAX` and conversions to
+/// `usize` saturate.
 pub trait Index:
-    Copy
-    + Clone
-    + Debug
-    + Display
-    + Into<usize>
-    + PartialEq
-    + Eq
-    + PartialOrd
-    + Ord
-    + Send
-    + Sync
-    + Sized
-    + 'static
+    Copy + Clone + Debug + Display + PartialEq + Eq + PartialOrd + Ord + Send + Sync + Sized + 'static
 {
     /// The zero value
     const ZERO: Self;
@@ -29,14 +22,12 @@ pub trait Index:
     /// the bounds are exceeded
     fn from_usize(val: usize) -> Self;
 
-    /// Try to create an instance of this type from a usize
+    /// Try to create an instance of this type from a usize, returning `None` if
+    /// the value is not representable
     fn try_from_usize(val: usize) -> Option<Self>;
 
-    /// Convert this instance into a `usize`
-    #[inline]
-    fn to_usize(self) -> usize {
-        self.into()
-    }
+    /// Convert this instance into a `usize`, saturating at `usize::MAX`
+    fn to_usize(self) -> usize;
 
     /// Add a `usize` without exceeding the bounds of this type
     fn saturating_add(self, val: usize) -> Self;
@@ -46,6 +37,23 @@ pub trait Index:
 
     /// Multiply by a `usize` without exceeding the bounds of this type
     fn saturating_mul(self, val: usize) -> Self;
+
+    /// Add another index, returning `None` if the result exceeds the bounds
+    /// of this type
+    #[inline]
+    fn checked_add(self, other: Self) -> Option<Self> {
+        self.to_usize()
+            .checked_add(other.to_usize())
+            .and_then(Self::try_from_usize)
+    }
+
+    /// Subtract another index, returning `None` if the result would be negative
+    #[inline]
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        self.to_usize()
+            .checked_sub(other.to_usize())
+            .and_then(Self::try_from_usize)
+    }
 }
 
 impl Index for u8 {
@@ -81,8 +89,78 @@ impl Index for u8 {
     fn saturating_mul(self, val: usize) -> Self {
         self.to_usize().saturating_mul(val).min(Self::MAX_USIZE) as Self
     }
+
+    #[inline]
+    fn checked_add(self, other: Self) -> Option<Self> {
+        self.checked_add(other)
+    }
+
+    #[inline]
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_sub(other)
+    }
 }
 
+macro_rules! impl_index {
+    ($($t:ty),+) => {
+        $(
+            impl Index for $t {
+                const ZERO: Self = 0;
+                const MAX_USIZE: usize = if (<$t>::MAX as u128) < (usize::MAX as u128) {
+                    <$t>::MAX as usize
+                } else {
+                    usize::MAX
+                };
+
+                #[inline]
+                fn from_usize(val: usize) -> Self {
+                    match Self::try_from_usize(val) {
+                        Some(val) => val,
+                        None => panic!("Index value exceeds the bounds of its type"),
+                    }
+                }
+
+                #[inline]
+                fn try_from_usize(val: usize) -> Option<Self> {
+                    val.try_into().ok()
+                }
+
+                #[inline]
+                fn to_usize(self) -> usize {
+                    self.try_into().unwrap_or(usize::MAX)
+                }
+
+                #[inline]
+                fn saturating_add(self, val: usize) -> Self {
+                    self.to_usize().saturating_add(val).min(Self::MAX_USIZE) as Self
+                }
+
+                #[inline]
+                fn saturating_sub(self, val: usize) -> Self {
+                    self.to_usize().saturating_sub(val) as Self
+                }
+
+                #[inline]
+                fn saturating_mul(self, val: usize) -> Self {
+                    self.to_usize().saturating_mul(val).min(Self::MAX_USIZE) as Self
+                }
+
+                #[inline]
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    self.checked_add(other)
+                }
+
+                #[inline]
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    self.checked_sub(other)
+                }
+            }
+        )+
+    };
+}
+
+impl_index!(u16, u32, u64);
+
 impl Index for usize {
     const ZERO: Self = 0usize;
     const MAX_USIZE: usize = usize::MAX;
@@ -97,6 +175,11 @@ impl Index for usize {
         Some(val)
     }
 
+    #[inline]
+    fn to_usize(self) -> usize {
+        self
+    }
+
     fn saturating_add(self, val: usize) -> Self {
         self.saturating_add(val)
     }
@@ -108,12 +191,35 @@ impl Index for usize {
     fn saturating_mul(self, val: usize) -> Self {
         self.saturating_mul(val)
     }
+
+    #[inline]
+    fn checked_add(self, other: Self) -> Option<Self> {
+        self.checked_add(other)
+    }
+
+    #[inline]
+    fn checked_sub(self, other: Self) -> Option<Self> {
+        self.checked_sub(other)
+    }
 }
 
 /// Growth behavior for collections which have exceeded their available storage
 pub trait Grow: Debug {
+    /// The maximum number of items which may be reserved by a collection
+    const MAX_CAPACITY: usize = usize::MAX;
+
     /// Calculate the next capacity to request from the allocator
     fn next_capacity<T, I: Index>(prev: I, minimum: I) -> I;
+
+    /// Calculate the capacity to request from the allocator when shrinking a
+    /// collection with a capacity of `current` to hold at least `minimum` items.
+    /// Results below `minimum` are ignored, and no reallocation is performed unless
+    /// the result is below `current`. By default this returns `minimum`.
+    #[inline]
+    fn shrink_capacity<T, I: Index>(current: I, minimum: I) -> I {
+        let _ = current;
+        minimum
+    }
 }
 
 /// Growth behavior which never requests extra capacity
@@ -142,3 +248,48 @@ impl Grow for GrowDoubling {
         preferred.max(minimum)
     }
 }
+
+/// Growth behavior which consistently doubles in size, without exceeding a capacity
+/// of `MAX` items. Attempts to reserve capacity beyond `MAX` items will fail with
+/// a `CapacityLimit` error.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GrowDoublingCapped<const MAX: usize>;
+
+impl<const MAX: usize> Grow for GrowDoublingCapped<MAX> {
+    const MAX_CAPACITY: usize = MAX;
+
+    #[inline]
+    fn next_capacity<T, I: Index>(prev: I, minimum: I) -> I {
+        let preferred = GrowDoubling::next_capacity::<T, I>(prev, minimum);
+        if preferred.to_usize() > MAX {
+            I::from_usize(MAX).max(minimum)
+        } else {
+            preferred
+        }
+    }
+}
+
+/// Growth behavior which multiplies the previous capacity by the ratio `NUM / DEN`.
+/// For example, `GrowFactor<3, 2>` grows by a factor of 1.5.
+///
+/// If the calculated capacity is not representable by the index type, then the
+/// minimum required capacity is used instead.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct GrowFactor<const NUM: usize, const DEN: usize>;
+
+impl<const NUM: usize, const DEN: usize> Grow for GrowFactor<NUM, DEN> {
+    #[inline]
+    fn next_capacity<T, I: Index>(prev: I, minimum: I) -> I {
+        let preferred = if prev == I::ZERO {
+            Some(min_non_zero_cap::<T>())
+        } else {
+            prev.to_usize()
+                .checked_mul(NUM)
+                .and_then(|cap| cap.checked_div(DEN))
+        };
+        match preferred.and_then(I::try_from_usize) {
+            Some(preferred) => preferred.max(minimum),
+            None => minimum,
+        }
+    }
+}
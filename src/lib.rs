@@ -1,6 +1,7 @@
 //! Data structures with extra flexible storage.
 
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "nightly", feature(extend_one))]
 #![warn(missing_docs)]
 
 #[doc = include_str!("../README.md")]
@@ -1,8 +1,12 @@
-use core::alloc::Layout;
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::Cell;
 use core::fmt;
 use core::marker::PhantomData;
 use core::mem::{align_of, ManuallyDrop};
+use core::ops::DerefMut;
 use core::ptr::{self, NonNull};
+#[cfg(target_has_atomic = "ptr")]
+use core::sync::atomic::{AtomicUsize, Ordering};
 
 use const_default::ConstDefault;
 
@@ -10,9 +14,7 @@ use const_default::ConstDefault;
 pub use allocator_api2::alloc::{Allocator, Global};
 
 #[cfg(all(feature = "alloc", not(feature = "allocator-api2")))]
-use alloc::alloc::{alloc as raw_alloc, dealloc as raw_dealloc};
-#[cfg(all(feature = "alloc", not(feature = "allocator-api2")))]
-use core::mem::transmute;
+use alloc::alloc::{alloc as raw_alloc, dealloc as raw_dealloc, realloc as raw_realloc};
 
 use crate::error::StorageError;
 
@@ -138,6 +140,21 @@ impl<A: Allocator> RawAlloc for A {
             .map_err(|_| StorageError::AllocError)
     }
 
+    #[inline]
+    unsafe fn try_resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, StorageError> {
+        if new_layout.size() >= old_layout.size() {
+            self.grow(ptr, old_layout, new_layout)
+        } else {
+            self.shrink(ptr, old_layout, new_layout)
+        }
+        .map_err(|_| StorageError::AllocError)
+    }
+
     #[inline]
     unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
         self.deallocate(ptr, layout)
@@ -150,10 +167,7 @@ impl RawAlloc for Global {
     fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
         let ptr = if layout.size() == 0 {
             // FIXME: use Layout::dangling when stabilized
-            #[allow(clippy::useless_transmute)]
-            unsafe {
-                NonNull::new_unchecked(transmute(layout.align()))
-            }
+            unsafe { NonNull::new_unchecked(layout.align() as *mut u8) }
         } else {
             let Some(ptr) = NonNull::new(unsafe { raw_alloc(layout) }) else {
                 return Err(StorageError::AllocError);
@@ -163,6 +177,33 @@ impl RawAlloc for Global {
         Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
     }
 
+    unsafe fn try_resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, StorageError> {
+        if old_layout.size() == 0 || new_layout.size() == 0 {
+            // nothing to copy
+            let new_ptr = self.try_alloc(new_layout)?;
+            self.release(ptr, old_layout);
+            return Ok(new_ptr);
+        }
+        if old_layout.align() == new_layout.align() {
+            // the allocation may be resized in place
+            let Some(ptr) = NonNull::new(raw_realloc(ptr.as_ptr(), old_layout, new_layout.size()))
+            else {
+                return Err(StorageError::AllocError);
+            };
+            return Ok(NonNull::slice_from_raw_parts(ptr, new_layout.size()));
+        }
+        let new_ptr = self.try_alloc(new_layout)?;
+        let cp_len = old_layout.size().min(new_layout.size());
+        ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr().cast(), cp_len);
+        self.release(ptr, old_layout);
+        Ok(new_ptr)
+    }
+
     #[inline]
     unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
         if layout.size() > 0 {
@@ -189,6 +230,261 @@ impl RawAllocDefault for Global {
     const DEFAULT: Self = Global;
 }
 
+/// An allocator which allocates via `A`, while tracking the number of live
+/// allocations and the total number of bytes currently allocated.
+///
+/// Zero-sized allocations are not counted. Cloning the allocator produces
+/// a new instance with its counters reset.
+///
+/// This type is only available on targets supporting pointer-sized atomic
+/// operations.
+#[cfg(target_has_atomic = "ptr")]
+#[derive(Debug, Default)]
+pub struct CountingAlloc<A> {
+    alloc: A,
+    bytes: AtomicUsize,
+    count: AtomicUsize,
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<A> CountingAlloc<A> {
+    /// Create a new counting allocator wrapping `alloc`.
+    #[inline]
+    pub const fn new(alloc: A) -> Self {
+        Self {
+            alloc,
+            bytes: AtomicUsize::new(0),
+            count: AtomicUsize::new(0),
+        }
+    }
+
+    /// Get the total number of bytes currently allocated.
+    #[inline]
+    pub fn allocated_bytes(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of allocations which have not been released.
+    #[inline]
+    pub fn live_allocations(&self) -> usize {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    /// Access the wrapped allocator.
+    #[inline]
+    pub fn inner(&self) -> &A {
+        &self.alloc
+    }
+
+    #[inline]
+    fn record_alloc(&self, size: usize) {
+        if size > 0 {
+            self.bytes.fetch_add(size, Ordering::Relaxed);
+            self.count.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[inline]
+    fn record_release(&self, size: usize) {
+        if size > 0 {
+            self.bytes.fetch_sub(size, Ordering::Relaxed);
+            self.count.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<A: Clone> Clone for CountingAlloc<A> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self::new(self.alloc.clone())
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<A: RawAlloc> RawAlloc for CountingAlloc<A> {
+    #[inline]
+    fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        let ptr = self.alloc.try_alloc(layout)?;
+        self.record_alloc(layout.size());
+        Ok(ptr)
+    }
+
+    #[inline]
+    fn try_alloc_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        let ptr = self.alloc.try_alloc_zeroed(layout)?;
+        self.record_alloc(layout.size());
+        Ok(ptr)
+    }
+
+    #[inline]
+    unsafe fn try_resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, StorageError> {
+        let ptr = self.alloc.try_resize(ptr, old_layout, new_layout)?;
+        self.record_alloc(new_layout.size());
+        self.record_release(old_layout.size());
+        Ok(ptr)
+    }
+
+    #[inline]
+    unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.alloc.release(ptr, layout);
+        self.record_release(layout.size());
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<A: RawAlloc> RawAlloc for &CountingAlloc<A> {
+    #[inline]
+    fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        (*self).try_alloc(layout)
+    }
+
+    #[inline]
+    fn try_alloc_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        (*self).try_alloc_zeroed(layout)
+    }
+
+    #[inline]
+    unsafe fn try_resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, StorageError> {
+        (*self).try_resize(ptr, old_layout, new_layout)
+    }
+
+    #[inline]
+    unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
+        (*self).release(ptr, layout)
+    }
+}
+
+#[cfg(target_has_atomic = "ptr")]
+impl<A: RawAllocDefault> RawAllocDefault for CountingAlloc<A> {
+    const DEFAULT: Self = Self::new(A::DEFAULT);
+}
+
+/// An allocator which allocates via `A`, while raising the alignment of each
+/// allocation to at least `ALIGN` bytes. `ALIGN` must be a power of two.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct AlignedAlloc<A, const ALIGN: usize>(pub A);
+
+impl<A, const ALIGN: usize> AlignedAlloc<A, ALIGN> {
+    #[inline]
+    fn aligned_layout(layout: Layout) -> Result<Layout, StorageError> {
+        Ok(layout.align_to(ALIGN)?)
+    }
+}
+
+impl<A: RawAlloc, const ALIGN: usize> RawAlloc for AlignedAlloc<A, ALIGN> {
+    #[inline]
+    fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        self.0.try_alloc(Self::aligned_layout(layout)?)
+    }
+
+    #[inline]
+    fn try_alloc_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        self.0.try_alloc_zeroed(Self::aligned_layout(layout)?)
+    }
+
+    #[inline]
+    unsafe fn try_resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, StorageError> {
+        self.0.try_resize(
+            ptr,
+            Self::aligned_layout(old_layout)?,
+            Self::aligned_layout(new_layout)?,
+        )
+    }
+
+    #[inline]
+    unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
+        // the layout was validated when the allocation was produced
+        self.0
+            .release(ptr, layout.align_to(ALIGN).unwrap_unchecked())
+    }
+}
+
+impl<A: RawAllocDefault, const ALIGN: usize> RawAllocDefault for AlignedAlloc<A, ALIGN> {
+    const DEFAULT: Self = Self(A::DEFAULT);
+}
+
+/// An adapter which supports the use of a `core::alloc::GlobalAlloc` implementation
+/// as an allocation target.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct GlobalAllocAdapter<G>(pub G);
+
+impl<G: GlobalAlloc> GlobalAllocAdapter<G> {
+    #[inline]
+    fn wrap_alloc(ptr: *mut u8, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        let Some(ptr) = NonNull::new(ptr) else {
+            return Err(StorageError::AllocError);
+        };
+        Ok(NonNull::slice_from_raw_parts(ptr, layout.size()))
+    }
+}
+
+impl<G: GlobalAlloc> RawAlloc for GlobalAllocAdapter<G> {
+    #[inline]
+    fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        if layout.size() == 0 {
+            // FIXME: use Layout::dangling when stabilized
+            let ptr = unsafe { NonNull::new_unchecked(layout.align() as *mut u8) };
+            return Ok(NonNull::slice_from_raw_parts(ptr, 0));
+        }
+        Self::wrap_alloc(unsafe { self.0.alloc(layout) }, layout)
+    }
+
+    #[inline]
+    fn try_alloc_zeroed(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        if layout.size() == 0 {
+            return self.try_alloc(layout);
+        }
+        Self::wrap_alloc(unsafe { self.0.alloc_zeroed(layout) }, layout)
+    }
+
+    unsafe fn try_resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, StorageError> {
+        if old_layout.size() > 0
+            && new_layout.size() > 0
+            && old_layout.align() == new_layout.align()
+        {
+            return Self::wrap_alloc(
+                self.0.realloc(ptr.as_ptr(), old_layout, new_layout.size()),
+                new_layout,
+            );
+        }
+        let new_ptr = self.try_alloc(new_layout)?;
+        let cp_len = old_layout.size().min(new_layout.size());
+        if cp_len > 0 {
+            ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr().cast(), cp_len);
+        }
+        self.release(ptr, old_layout);
+        Ok(new_ptr)
+    }
+
+    #[inline]
+    unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
+        if layout.size() > 0 {
+            self.0.dealloc(ptr.as_ptr(), layout);
+        }
+    }
+}
+
 pub trait AllocHeader: Copy + Clone + Sized {
     const EMPTY: Self;
 
@@ -715,6 +1011,18 @@ impl<A: RawAlloc> SpillAlloc<'_, A> {
             _fixed: FixedAlloc::NEW,
         }
     }
+
+    /// Determine whether a pointer refers to the initial fixed storage buffer.
+    #[inline]
+    pub fn is_fixed_storage(&self, ptr: *const u8) -> bool {
+        !self.initial.is_null() && ptr::eq(self.initial, ptr)
+    }
+
+    /// Access the allocator used once the fixed storage buffer is exhausted.
+    #[inline]
+    pub fn inner(&self) -> &A {
+        &self.alloc
+    }
 }
 
 impl<A: RawAlloc> RawAlloc for SpillAlloc<'_, A> {
@@ -741,6 +1049,13 @@ impl<'a, A: RawAllocDefault> ConstDefault for SpillAlloc<'a, A> {
     const DEFAULT: Self = Self::new(A::DEFAULT, ptr::null());
 }
 
+// SAFETY: the pointer to the initial fixed storage is only used for comparison,
+// and the fixed storage itself is owned by the collection using this allocator
+unsafe impl<A: Send> Send for SpillAlloc<'_, A> {}
+
+// SAFETY: the pointer to the initial fixed storage is never dereferenced
+unsafe impl<A: Sync> Sync for SpillAlloc<'_, A> {}
+
 /// An allocator which consumes the provided fixed storage before deferring to the
 /// contained `A` instance allocator for further allocations
 #[derive(Debug, Default, Clone)]
@@ -793,6 +1108,94 @@ where
     }
 }
 
+/// An allocator which consumes the provided fixed storage for the first
+/// allocation it is able to satisfy, before deferring to the contained `A`
+/// instance allocator for further allocations.
+///
+/// When used as the allocator for a [`SpillStorage`], this produces a second
+/// tier of fixed storage which is used before any dynamic allocations are made.
+/// The fixed storage is only claimed by the first allocation request which it
+/// is able to satisfy.
+pub struct ChainAlloc<'a, I, A> {
+    buffer: Cell<Option<I>>,
+    fixed: Cell<*const u8>,
+    alloc: A,
+    _pd: PhantomData<&'a mut ()>,
+}
+
+impl<'a, I, A> ChainAlloc<'a, I, A>
+where
+    I: DerefMut,
+    for<'b> &'b mut I::Target: RawAllocIn<RawAlloc = FixedAlloc<'b>>,
+    A: RawAlloc,
+{
+    /// Create a new chained allocator from a fixed storage buffer and an
+    /// allocator instance.
+    #[inline]
+    pub const fn new(buffer: I, alloc: A) -> Self {
+        Self {
+            buffer: Cell::new(Some(buffer)),
+            fixed: Cell::new(ptr::null()),
+            alloc,
+            _pd: PhantomData,
+        }
+    }
+
+    /// Determine whether a pointer refers to the fixed storage buffer.
+    #[inline]
+    pub fn is_fixed_storage(&self, ptr: *const u8) -> bool {
+        let fixed = self.fixed.get();
+        !fixed.is_null() && ptr::eq(fixed, ptr)
+    }
+}
+
+impl<I, A: fmt::Debug> fmt::Debug for ChainAlloc<'_, I, A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChainAlloc")
+            .field("alloc", &self.alloc)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, I, A> RawAlloc for ChainAlloc<'a, I, A>
+where
+    I: DerefMut,
+    for<'b> &'b mut I::Target: RawAllocIn<RawAlloc = FixedAlloc<'b>>,
+    A: RawAlloc,
+{
+    fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        if let Some(mut buffer) = self.buffer.take() {
+            match (&mut *buffer).try_alloc_in(layout) {
+                Ok((ptr, _fixed)) => {
+                    self.fixed.set(ptr.as_ptr().cast());
+                    return Ok(ptr);
+                }
+                Err(StorageError::CapacityLimit) => {
+                    // the fixed storage remains available for a smaller request
+                    self.buffer.set(Some(buffer));
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        self.alloc.try_alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
+        if !self.is_fixed_storage(ptr.as_ptr()) {
+            self.alloc.release(ptr, layout)
+        }
+    }
+}
+
+// SAFETY: the pointer to the fixed storage is only used for comparison. The
+// allocator is not `Sync`, as the fixed storage is claimed through a shared reference
+unsafe impl<I: Send, A: Send> Send for ChainAlloc<'_, I, A> {}
+
+/// An allocator which consumes the fixed storage `I1`, followed by the fixed
+/// storage `I2`, before deferring to the allocator instance `A`.
+pub type ChainStorage<'a, I1, I2, A> = SpillStorage<'a, I1, ChainAlloc<'a, I2, A>>;
+
 /// A ZST representing the 'thin' allocation strategy, where data pointers
 /// are thin and any metadata is stored within the allocation
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
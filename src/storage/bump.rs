@@ -0,0 +1,225 @@
+use core::alloc::Layout;
+use core::cell::Cell;
+use core::fmt;
+use core::mem::size_of;
+use core::ptr::{self, NonNull};
+
+use crate::error::StorageError;
+
+use super::alloc::{Global, RawAlloc};
+
+const DEFAULT_BLOCK_SIZE: usize = 4096;
+
+struct BlockHeader {
+    prev: Option<NonNull<BlockHeader>>,
+    layout: Layout,
+}
+
+/// A simple arena allocator, which hands out memory from a chain of blocks
+/// obtained from the allocator `A`.
+///
+/// Allocations are performed by advancing a cursor within the current block,
+/// and a new block is requested once the current one is exhausted. Releasing
+/// an individual allocation has no effect: all blocks are returned to `A`
+/// at once when the arena is dropped or reset.
+pub struct Bump<A: RawAlloc = Global> {
+    alloc: A,
+    block_size: usize,
+    current: Cell<Option<NonNull<BlockHeader>>>,
+    cursor: Cell<*mut u8>,
+    end: Cell<*mut u8>,
+}
+
+impl Bump<Global> {
+    /// Create a new, empty arena which obtains blocks from the global allocator.
+    #[inline]
+    pub const fn new() -> Self {
+        Self::new_in(Global)
+    }
+}
+
+impl<A: RawAlloc> Bump<A> {
+    /// Create a new, empty arena which obtains blocks from the allocator `alloc`.
+    #[inline]
+    pub const fn new_in(alloc: A) -> Self {
+        Self::with_block_size_in(DEFAULT_BLOCK_SIZE, alloc)
+    }
+
+    /// Create a new, empty arena which obtains blocks of (at least) `block_size`
+    /// bytes from the allocator `alloc`.
+    #[inline]
+    pub const fn with_block_size_in(block_size: usize, alloc: A) -> Self {
+        Self {
+            alloc,
+            block_size,
+            current: Cell::new(None),
+            cursor: Cell::new(ptr::null_mut()),
+            end: Cell::new(ptr::null_mut()),
+        }
+    }
+
+    /// Access the allocator instance used to obtain blocks.
+    #[inline]
+    pub fn allocator(&self) -> &A {
+        &self.alloc
+    }
+
+    /// Get the number of blocks currently held by the arena.
+    pub fn block_count(&self) -> usize {
+        let mut count = 0;
+        let mut next = self.current.get();
+        while let Some(block) = next {
+            count += 1;
+            next = unsafe { block.as_ref() }.prev;
+        }
+        count
+    }
+
+    /// Release all blocks held by the arena. Exclusive access ensures that no
+    /// allocations from the arena remain in use.
+    pub fn reset(&mut self) {
+        let mut next = self.current.take();
+        while let Some(block) = next {
+            let BlockHeader { prev, layout } = unsafe { block.as_ptr().read() };
+            unsafe { self.alloc.release(block.cast(), layout) };
+            next = prev;
+        }
+        self.cursor.set(ptr::null_mut());
+        self.end.set(ptr::null_mut());
+    }
+
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.end.get() as usize - self.cursor.get() as usize
+    }
+
+    fn try_alloc_block(&self, layout: Layout) -> Result<(), StorageError> {
+        let data_size = layout
+            .size()
+            .checked_add(layout.align())
+            .ok_or(StorageError::CapacityLimit)?
+            .max(self.block_size);
+        let block_layout = Layout::new::<BlockHeader>()
+            .extend(Layout::from_size_align(data_size, 1)?)?
+            .0;
+        let block = self.alloc.try_alloc(block_layout)?;
+        let head = block.cast::<BlockHeader>();
+        unsafe {
+            head.as_ptr().write(BlockHeader {
+                prev: self.current.get(),
+                layout: block_layout,
+            })
+        };
+        let base = block.cast::<u8>().as_ptr();
+        self.current.set(Some(head));
+        self.cursor
+            .set(unsafe { base.add(size_of::<BlockHeader>()) });
+        self.end.set(unsafe { base.add(block.len()) });
+        Ok(())
+    }
+}
+
+impl<A: RawAlloc> RawAlloc for Bump<A> {
+    fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        if layout.size() == 0 {
+            // FIXME: use Layout::dangling when stabilized
+            let ptr = unsafe { NonNull::new_unchecked(layout.align() as *mut u8) };
+            return Ok(NonNull::slice_from_raw_parts(ptr, 0));
+        }
+        let mut offset = self.cursor.get().align_offset(layout.align());
+        if self.current.get().is_none()
+            || offset > self.remaining()
+            || layout.size() > self.remaining() - offset
+        {
+            self.try_alloc_block(layout)?;
+            offset = self.cursor.get().align_offset(layout.align());
+        }
+        let ptr = unsafe { self.cursor.get().add(offset) };
+        self.cursor.set(unsafe { ptr.add(layout.size()) });
+        Ok(NonNull::slice_from_raw_parts(
+            unsafe { NonNull::new_unchecked(ptr) },
+            layout.size(),
+        ))
+    }
+
+    unsafe fn try_resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, StorageError> {
+        if old_layout.size() > 0 && new_layout.align() <= old_layout.align() {
+            if new_layout.size() <= old_layout.size() {
+                // shrink in place
+                return Ok(NonNull::slice_from_raw_parts(ptr, new_layout.size()));
+            }
+            let old_end = ptr.as_ptr().add(old_layout.size());
+            if old_end == self.cursor.get()
+                && new_layout.size() - old_layout.size() <= self.remaining()
+            {
+                // grow the most recent allocation in place
+                self.cursor.set(ptr.as_ptr().add(new_layout.size()));
+                return Ok(NonNull::slice_from_raw_parts(ptr, new_layout.size()));
+            }
+        }
+        let new_ptr = self.try_alloc(new_layout)?;
+        let cp_len = old_layout.size().min(new_layout.size());
+        if cp_len > 0 {
+            ptr::copy_nonoverlapping(ptr.as_ptr(), new_ptr.as_ptr().cast(), cp_len);
+        }
+        Ok(new_ptr)
+    }
+
+    #[inline]
+    unsafe fn release(&self, _ptr: NonNull<u8>, _layout: Layout) {
+        // memory is reclaimed when the arena is dropped or reset
+    }
+}
+
+impl<A: RawAlloc> RawAlloc for &Bump<A> {
+    #[inline]
+    fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        (*self).try_alloc(layout)
+    }
+
+    #[inline]
+    unsafe fn try_resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, StorageError> {
+        (*self).try_resize(ptr, old_layout, new_layout)
+    }
+
+    #[inline]
+    unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
+        (*self).release(ptr, layout)
+    }
+}
+
+impl<A: RawAlloc + fmt::Debug> fmt::Debug for Bump<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Bump")
+            .field("alloc", &self.alloc)
+            .field("block_size", &self.block_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<A: RawAlloc + Default> Default for Bump<A> {
+    #[inline]
+    fn default() -> Self {
+        Self::new_in(A::default())
+    }
+}
+
+impl<A: RawAlloc> Drop for Bump<A> {
+    fn drop(&mut self) {
+        self.reset();
+    }
+}
+
+// SAFETY: the arena exclusively owns its blocks, and shared access
+// (which permits allocation) is not transferred between threads
+unsafe impl<A: RawAlloc + Send> Send for Bump<A> {}
@@ -1,10 +1,28 @@
 //! Backing storage types for collections.
+//!
+//! ### Thread safety
+//!
+//! The fixed storage buffers [`ArrayStorage`] and [`ByteStorage`], and
+//! mutable references to them, are `Send` and `Sync` when `T` is.
+//! [`FixedAlloc`] is always `Send` and `Sync`, while [`SpillStorage`] and
+//! [`SpillAlloc`] are `Send` or `Sync` when their components are, so that a
+//! collection backed by a stack buffer may be shared with scoped threads.
+//! [`ChainAlloc`] and [`Bump`] are `Send` when their components are, but
+//! are never `Sync` because they allocate through a shared reference.
+//!
+//! ```compile_fail
+//! fn is_sync<T: Sync>() {}
+//! is_sync::<flex_alloc::storage::Bump>();
+//! ```
 
 use core::fmt;
-use core::mem::{ManuallyDrop, MaybeUninit};
+use core::marker::PhantomData;
+use core::mem::{align_of, size_of, ManuallyDrop, MaybeUninit};
 
 pub(crate) mod alloc;
 
+pub(crate) mod bump;
+
 pub(crate) mod utils;
 
 #[cfg(feature = "zeroize")]
@@ -12,8 +30,17 @@ pub(crate) mod zero;
 
 use const_default::ConstDefault;
 
-pub use self::alloc::{FixedAlloc, Global, RawAlloc, RawAllocIn, SpillAlloc, SpillStorage, Thin};
+pub use self::bump::Bump;
+
+pub use self::alloc::{
+    AlignedAlloc, ChainAlloc, ChainStorage, FixedAlloc, Global, GlobalAllocAdapter, RawAlloc,
+    RawAllocIn, SpillAlloc, SpillStorage, Thin,
+};
+
+#[cfg(target_has_atomic = "ptr")]
+pub use self::alloc::CountingAlloc;
 use crate::error::StorageError;
+use crate::index::Index;
 
 #[cfg(feature = "zeroize")]
 pub use self::zero::ZeroizingAlloc;
@@ -57,6 +84,24 @@ impl<T, const N: usize> ArrayStorage<T, N> {
     /// Constant initializer.
     pub const DEFAULT: Self = Self(unsafe { MaybeUninit::uninit().assume_init() });
 
+    /// Create a new storage buffer, initialized with the contents of an array.
+    ///
+    /// The storage buffer does not track initialization, so the contained items
+    /// will not be dropped unless ownership is taken by a collection.
+    pub const fn from_array(data: [T; N]) -> Self {
+        #[repr(C)]
+        union Transmute<T, const N: usize> {
+            array: ManuallyDrop<[T; N]>,
+            storage: ManuallyDrop<[MaybeUninit<T>; N]>,
+        }
+
+        let cast = Transmute {
+            array: ManuallyDrop::new(data),
+        };
+        // SAFETY: `[T; N]` and `[MaybeUninit<T>; N]` have the same layout
+        Self(ManuallyDrop::into_inner(unsafe { cast.storage }))
+    }
+
     /// Access the buffer contents as a mutable slice.
     pub fn as_uninit_slice(&mut self) -> &mut [MaybeUninit<T>] {
         &mut self.0
@@ -81,7 +126,16 @@ impl<'a, T: 'a, const N: usize> WithAlloc<'a> for &'a mut ArrayStorage<T, N> {
 
     #[inline]
     fn with_alloc_in<A: RawAlloc + 'a>(self, alloc: A) -> Self::NewIn<A> {
-        SpillStorage::new_in(&mut self.0, alloc)
+        self.0.as_mut_slice().with_alloc_in(alloc)
+    }
+}
+
+impl<'a, T: 'a> WithAlloc<'a> for &'a mut [MaybeUninit<T>] {
+    type NewIn<A: 'a> = SpillStorage<'a, &'a mut [MaybeUninit<T>], A>;
+
+    #[inline]
+    fn with_alloc_in<A: RawAlloc + 'a>(self, alloc: A) -> Self::NewIn<A> {
+        SpillStorage::new_in(self, alloc)
     }
 }
 
@@ -98,6 +152,24 @@ impl<T, const N: usize> ByteStorage<T, N> {
         data: unsafe { MaybeUninit::uninit().assume_init() },
     };
 
+    const CHECK_CAPACITY: () = assert!(
+        Self::capacity_for::<T>() > 0,
+        "byte storage cannot hold an instance of the aligned type"
+    );
+
+    /// Calculate the number of items of type `U` which are guaranteed to fit
+    /// within this storage buffer, accounting for any padding required to satisfy
+    /// the alignment of `U`.
+    pub const fn capacity_for<U>() -> usize {
+        let size = size_of::<U>();
+        if size == 0 {
+            return usize::MAX;
+        }
+        // the buffer is only guaranteed to be aligned for `T`
+        let padding = align_of::<U>().saturating_sub(align_of::<T>());
+        N.saturating_sub(padding) / size
+    }
+
     /// Access the buffer contents as a mutable slice.
     pub fn as_uninit_slice(&mut self) -> &mut [MaybeUninit<u8>] {
         unsafe { &mut self.data }
@@ -142,19 +214,44 @@ pub const fn aligned_byte_storage<T, const N: usize>() -> ByteStorage<T, N> {
     ByteStorage::DEFAULT
 }
 
+/// Create a new byte storage buffer for a maximum byte capacity `N`, with
+/// a memory alignment matching type `T`. Compilation will fail if the buffer
+/// is not large enough to hold at least one instance of `T`.
+///
+/// ```compile_fail
+/// let buf = flex_alloc::storage::checked_aligned_byte_storage::<u64, 4>();
+/// ```
+pub const fn checked_aligned_byte_storage<T, const N: usize>() -> ByteStorage<T, N> {
+    let () = ByteStorage::<T, N>::CHECK_CAPACITY;
+    ByteStorage::DEFAULT
+}
+
 /// A marker type used to indicate the inline allocation strategy, which
 /// stores all items within the collection handle.
 #[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Inline<const N: usize>;
 
+/// A marker type used to indicate the inline allocation strategy, storing
+/// the collection length as the compact index type `L` (`u8` by default).
+///
+/// The capacity `N` must be representable by `L`, otherwise a compile-time
+/// error will be raised when the collection is instantiated.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct InlineSmall<const N: usize, L = u8>(PhantomData<L>);
+
+impl<const N: usize, L> InlineSmall<N, L> {
+    /// Constant initializer.
+    pub const DEFAULT: Self = Self(PhantomData);
+}
+
 /// An inline storage buffer.
 #[derive(Debug)]
-pub struct InlineBuffer<T, const N: usize> {
+pub struct InlineBuffer<T, const N: usize, L: Index = usize> {
     pub(crate) storage: ArrayStorage<T, N>,
-    pub(crate) length: usize,
+    pub(crate) length: L,
 }
 
-impl<T, const N: usize> InlineBuffer<T, N> {
+impl<T, const N: usize, L: Index> InlineBuffer<T, N, L> {
     pub(crate) fn try_for_capacity(capacity: usize, exact: bool) -> Result<Self, StorageError> {
         if (!exact && capacity < N) || capacity == N {
             Ok(Self::DEFAULT)
@@ -164,21 +261,27 @@ impl<T, const N: usize> InlineBuffer<T, N> {
     }
 }
 
-impl<T, const N: usize> ConstDefault for InlineBuffer<T, N> {
-    const DEFAULT: Self = Self {
-        storage: ArrayStorage::DEFAULT,
-        length: 0,
+impl<T, const N: usize, L: Index> ConstDefault for InlineBuffer<T, N, L> {
+    const DEFAULT: Self = {
+        assert!(
+            N <= L::MAX_USIZE,
+            "inline capacity exceeds the range of the length type"
+        );
+        Self {
+            storage: ArrayStorage::DEFAULT,
+            length: L::ZERO,
+        }
     };
 }
 
-impl<T, const N: usize> Default for InlineBuffer<T, N> {
+impl<T, const N: usize, L: Index> Default for InlineBuffer<T, N, L> {
     #[inline]
     fn default() -> Self {
         Self::DEFAULT
     }
 }
 
-impl<'a, T: 'a, const N: usize> RawBuffer for InlineBuffer<T, N> {
+impl<T, const N: usize, L: Index> RawBuffer for InlineBuffer<T, N, L> {
     type RawData = T;
 
     #[inline]
@@ -2,15 +2,23 @@
 
 use core::alloc::Layout;
 use core::fmt::Debug;
+use core::marker::PhantomData;
 use core::mem::{size_of, MaybeUninit};
 use core::slice;
 
 use crate::error::StorageError;
-use crate::index::Index;
+use crate::index::{Grow, Index};
 use crate::storage::alloc::{AllocHandle, AllocHeader, AllocLayout};
 use crate::storage::utils::array_layout;
 use crate::storage::{InlineBuffer, RawBuffer};
 
+#[cfg(feature = "alloc")]
+use {
+    crate::storage::alloc::{AllocHandleParts, FatAllocHandle, ThinAllocHandle},
+    crate::storage::{Global, RawAlloc},
+    core::ptr,
+};
+
 /// The header associated with each `Vec` instance.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct VecHeader<I: Index = usize> {
@@ -48,11 +56,12 @@ impl<T, I: Index> AllocLayout for VecData<T, I> {
     #[inline]
     fn update_header(header: &mut Self::Header, layout: Layout) {
         let t_size = size_of::<T>();
-        header.capacity = I::from_usize(if t_size > 0 {
-            (layout.size() / t_size).min(I::MAX_USIZE)
-        } else {
-            I::MAX_USIZE
-        });
+        header.capacity = I::from_usize(
+            layout
+                .size()
+                .checked_div(t_size)
+                .map_or(I::MAX_USIZE, |cap| cap.min(I::MAX_USIZE)),
+        );
     }
 }
 
@@ -113,6 +122,11 @@ pub trait VecBuffer: RawBuffer<RawData = Self::Item> {
     /// Attempt to resize this buffer to a new capacity. The `exact` flag determines
     /// whether a larger capacity would be acceptable.
     fn vec_try_resize(&mut self, capacity: Self::Index, exact: bool) -> Result<(), StorageError>;
+
+    /// Notify the buffer that the collection has been left with a length of zero
+    /// after removing items. By default this does nothing.
+    #[inline]
+    fn vec_cleared(&mut self) {}
 }
 
 impl<B, T, I: Index> VecBuffer for B
@@ -153,7 +167,7 @@ where
     }
 }
 
-impl<'a, T: 'a, const N: usize> VecBuffer for InlineBuffer<T, N> {
+impl<T, const N: usize, L: Index> VecBuffer for InlineBuffer<T, N, L> {
     type Item = T;
     type Index = usize;
 
@@ -164,12 +178,12 @@ impl<'a, T: 'a, const N: usize> VecBuffer for InlineBuffer<T, N> {
 
     #[inline]
     fn length(&self) -> usize {
-        self.length
+        self.length.to_usize()
     }
 
     #[inline]
     unsafe fn set_length(&mut self, len: usize) {
-        self.length = len;
+        self.length = L::from_usize(len);
     }
 
     #[inline]
@@ -181,3 +195,184 @@ impl<'a, T: 'a, const N: usize> VecBuffer for InlineBuffer<T, N> {
         }
     }
 }
+
+/// Shrink the capacity of a `Vec` buffer such that it holds at least its current
+/// length and `min_capacity` items. The growth strategy `G` may choose to retain
+/// additional capacity.
+pub(crate) fn vec_buffer_try_shrink<B: VecBuffer, G: Grow>(
+    buf: &mut B,
+    min_capacity: B::Index,
+) -> Result<(), StorageError> {
+    let capacity = buf.capacity();
+    let minimum = buf.length().max(min_capacity);
+    let target = G::shrink_capacity::<B::Item, _>(capacity, minimum).max(minimum);
+    if target < capacity {
+        buf.vec_try_resize(target, true)?;
+    }
+    Ok(())
+}
+
+/// A `Vec` buffer wrapper which shrinks the capacity of the wrapped buffer to
+/// at most `KEEP` items when the collection is cleared, according to the
+/// growth strategy `G`.
+#[derive(Debug)]
+#[repr(transparent)]
+pub struct AutoShrinkBuffer<B, G, const KEEP: usize>(pub(crate) B, PhantomData<G>);
+
+impl<B, G, const KEEP: usize> AutoShrinkBuffer<B, G, KEEP> {
+    #[inline]
+    pub(crate) const fn new(buffer: B) -> Self {
+        Self(buffer, PhantomData)
+    }
+}
+
+impl<B: RawBuffer, G, const KEEP: usize> RawBuffer for AutoShrinkBuffer<B, G, KEEP> {
+    type RawData = B::RawData;
+
+    #[inline]
+    fn data_ptr(&self) -> *const Self::RawData {
+        self.0.data_ptr()
+    }
+
+    #[inline]
+    fn data_ptr_mut(&mut self) -> *mut Self::RawData {
+        self.0.data_ptr_mut()
+    }
+}
+
+impl<B: VecBuffer, G: Grow, const KEEP: usize> VecBuffer for AutoShrinkBuffer<B, G, KEEP> {
+    type Item = B::Item;
+    type Index = B::Index;
+
+    #[inline]
+    fn capacity(&self) -> Self::Index {
+        self.0.capacity()
+    }
+
+    #[inline]
+    fn length(&self) -> Self::Index {
+        self.0.length()
+    }
+
+    #[inline]
+    unsafe fn set_length(&mut self, len: Self::Index) {
+        self.0.set_length(len)
+    }
+
+    #[inline]
+    fn vec_try_resize(&mut self, capacity: Self::Index, exact: bool) -> Result<(), StorageError> {
+        self.0.vec_try_resize(capacity, exact)
+    }
+
+    fn vec_cleared(&mut self) {
+        self.0.vec_cleared();
+        if self.0.capacity().to_usize() > KEEP {
+            // a failure to shrink the buffer is not considered an error
+            let _ = vec_buffer_try_shrink::<_, G>(&mut self.0, B::Index::from_usize(KEEP));
+        }
+    }
+}
+
+/// Support creation of a new, empty `Vec` buffer sharing the allocator of an
+/// existing buffer.
+pub trait VecBufferSpawn: VecBuffer + Sized {
+    /// Try to create a new, empty buffer with a minimum capacity.
+    fn vec_buffer_try_spawn(
+        &self,
+        capacity: Self::Index,
+        exact: bool,
+    ) -> Result<Self, StorageError>;
+}
+
+impl<B, T, I: Index> VecBufferSpawn for B
+where
+    B: AllocHandle<Meta = VecData<T, I>>,
+    B::Alloc: Clone,
+{
+    #[inline]
+    fn vec_buffer_try_spawn(&self, capacity: I, exact: bool) -> Result<Self, StorageError> {
+        self.spawn_handle(
+            VecHeader {
+                capacity,
+                length: I::ZERO,
+            },
+            exact,
+        )
+    }
+}
+
+impl<T, const N: usize, L: Index> VecBufferSpawn for InlineBuffer<T, N, L> {
+    #[inline]
+    fn vec_buffer_try_spawn(&self, capacity: usize, exact: bool) -> Result<Self, StorageError> {
+        InlineBuffer::try_for_capacity(capacity, exact)
+    }
+}
+
+impl<B: VecBufferSpawn, G: Grow, const KEEP: usize> VecBufferSpawn
+    for AutoShrinkBuffer<B, G, KEEP>
+{
+    #[inline]
+    fn vec_buffer_try_spawn(
+        &self,
+        capacity: Self::Index,
+        exact: bool,
+    ) -> Result<Self, StorageError> {
+        Ok(Self::new(self.0.vec_buffer_try_spawn(capacity, exact)?))
+    }
+}
+
+/// Support conversion of a `Vec` buffer into a standard boxed slice.
+#[cfg(feature = "alloc")]
+pub trait VecBufferBoxed: VecBuffer<Index = usize> {
+    /// Try to convert this buffer into a boxed slice. In the case of an error,
+    /// the buffer is returned unchanged.
+    fn vec_buffer_try_into_boxed(
+        self,
+    ) -> Result<alloc::boxed::Box<[Self::Item]>, (StorageError, Self)>;
+}
+
+#[cfg(feature = "alloc")]
+impl<T> VecBufferBoxed for FatAllocHandle<VecData<T, usize>, Global> {
+    fn vec_buffer_try_into_boxed(mut self) -> Result<alloc::boxed::Box<[T]>, (StorageError, Self)> {
+        let length = self.length();
+        if self.capacity() != length {
+            if let Err(err) = self.vec_try_resize(length, true) {
+                return Err((err, self));
+            }
+        }
+        let (_header, data, _alloc) = self.handle_into_parts();
+        let data = ptr::slice_from_raw_parts_mut(data.as_ptr(), length);
+        Ok(unsafe { alloc::boxed::Box::from_raw(data) })
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> VecBufferBoxed for ThinAllocHandle<VecData<T, usize>, Global> {
+    fn vec_buffer_try_into_boxed(mut self) -> Result<alloc::boxed::Box<[T]>, (StorageError, Self)> {
+        // the header is stored within the allocation, so the items are
+        // always moved into a new allocation
+        let length = self.length();
+        let data = match array_layout::<T>(length).and_then(|layout| Global.try_alloc(layout)) {
+            Ok(data) => data.cast::<T>(),
+            Err(err) => return Err((err, self)),
+        };
+        if length > 0 {
+            // SAFETY: `data` is a new allocation which cannot overlap the source
+            // buffer, and has the layout of `[T; length]`. The first `length` items
+            // of the source are initialized, and ownership of them is moved to
+            // `data`. The source length is reset before the source buffer is
+            // released, so the items are not dropped twice. Its capacity is
+            // non-zero as it holds at least one item.
+            unsafe {
+                ptr::copy_nonoverlapping(self.data_ptr(), data.as_ptr(), length);
+                self.set_length(0);
+            }
+        }
+        // SAFETY: `data` holds `length` initialized items in an allocation made by
+        // `Global` with the layout of a boxed slice. For an empty vector or a
+        // zero-sized `T` the layout is empty, and `data` is a dangling, aligned
+        // pointer as expected by `Box` for a zero-sized allocation.
+        let data = ptr::slice_from_raw_parts_mut(data.as_ptr(), length);
+        Ok(unsafe { alloc::boxed::Box::from_raw(data) })
+    }
+}
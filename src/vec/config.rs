@@ -14,10 +14,11 @@ use crate::storage::alloc::{
     ThinAllocHandle,
 };
 use crate::storage::{
-    ArrayStorage, Global, Inline, InlineBuffer, RawAlloc, RawAllocIn, SpillStorage, Thin,
+    ArrayStorage, Global, Inline, InlineBuffer, InlineSmall, RawAlloc, RawAllocIn, SpillStorage,
+    Thin,
 };
 
-use super::buffer::{VecBuffer, VecData, VecHeader};
+use super::buffer::{AutoShrinkBuffer, VecBuffer, VecData, VecHeader};
 
 /// Define the associated allocation handle for a `Vec` allocator.
 pub trait VecAllocHandle {
@@ -186,6 +187,18 @@ pub struct Custom<H: VecAllocHandle, I: Index = usize, G: Grow = GrowExact> {
     _pd: PhantomData<(I, G)>,
 }
 
+impl<H: VecAllocHandle, I: Index, G: Grow> Custom<H, I, G> {
+    /// Create a custom `Vec` definition using the allocator instance `alloc`,
+    /// which may be used as an allocation target.
+    #[inline]
+    pub const fn new(alloc: H::RawAlloc) -> Self {
+        Self {
+            alloc,
+            _pd: PhantomData,
+        }
+    }
+}
+
 impl<C, I: Index, G: Grow> ConstDefault for Custom<C, I, G>
 where
     C: VecAllocHandle,
@@ -249,6 +262,44 @@ where
     }
 }
 
+/// Wrap a `Vec` configuration such that clearing the vector, or otherwise removing
+/// all of its items (for example by truncating, draining or retaining), also
+/// shrinks its capacity to `KEEP` items. The growth strategy of `C` may choose to
+/// retain additional capacity (see [`Grow::shrink_capacity`]).
+///
+/// Errors encountered while shrinking the buffer are ignored.
+#[derive(Debug, Default)]
+pub struct AutoShrink<C, const KEEP: usize>(PhantomData<C>);
+
+impl<C: VecConfig, const KEEP: usize> VecConfig for AutoShrink<C, KEEP> {
+    type Buffer<T> = AutoShrinkBuffer<C::Buffer<T>, C::Grow, KEEP>;
+    type Grow = C::Grow;
+    type Index = C::Index;
+}
+
+impl<T, C: VecConfigNew<T>, const KEEP: usize> VecConfigNew<T> for AutoShrink<C, KEEP> {
+    const EMPTY_BUFFER: Self::Buffer<T> = AutoShrinkBuffer::new(C::EMPTY_BUFFER);
+
+    #[inline]
+    fn vec_buffer_try_new(
+        capacity: Self::Index,
+        exact: bool,
+    ) -> Result<Self::Buffer<T>, StorageError> {
+        C::vec_buffer_try_new(capacity, exact).map(AutoShrinkBuffer::new)
+    }
+}
+
+impl<T, C: VecConfigSpawn<T>, const KEEP: usize> VecConfigSpawn<T> for AutoShrink<C, KEEP> {
+    #[inline]
+    fn vec_buffer_try_spawn(
+        buf: &Self::Buffer<T>,
+        capacity: Self::Index,
+        exact: bool,
+    ) -> Result<Self::Buffer<T>, StorageError> {
+        C::vec_buffer_try_spawn(&buf.0, capacity, exact).map(AutoShrinkBuffer::new)
+    }
+}
+
 impl<const N: usize> VecConfig for Inline<N> {
     type Buffer<T> = InlineBuffer<T, N>;
     type Index = usize;
@@ -277,6 +328,34 @@ impl<T, const N: usize> VecConfigSpawn<T> for Inline<N> {
     }
 }
 
+impl<const N: usize, L: Index> VecConfig for InlineSmall<N, L> {
+    type Buffer<T> = InlineBuffer<T, N, L>;
+    type Index = usize;
+    type Grow = GrowExact;
+}
+
+impl<T, const N: usize, L: Index> VecConfigNew<T> for InlineSmall<N, L> {
+    const EMPTY_BUFFER: Self::Buffer<T> = InlineBuffer::<T, N, L>::DEFAULT;
+
+    fn vec_buffer_try_new(
+        capacity: Self::Index,
+        exact: bool,
+    ) -> Result<Self::Buffer<T>, StorageError> {
+        InlineBuffer::try_for_capacity(capacity, exact)
+    }
+}
+
+impl<T, const N: usize, L: Index> VecConfigSpawn<T> for InlineSmall<N, L> {
+    #[inline]
+    fn vec_buffer_try_spawn(
+        _buf: &Self::Buffer<T>,
+        capacity: Self::Index,
+        exact: bool,
+    ) -> Result<Self::Buffer<T>, StorageError> {
+        InlineBuffer::try_for_capacity(capacity, exact)
+    }
+}
+
 impl VecAllocHandle for Thin {
     type RawAlloc = Global;
     type AllocHandle<T, I: Index> = ThinAllocHandle<VecData<T, I>, Global>;
@@ -410,6 +489,19 @@ impl<T, const N: usize> VecNewIn<T> for Inline<N> {
     }
 }
 
+impl<T, const N: usize, L: Index> VecNewIn<T> for InlineSmall<N, L> {
+    type Config = InlineSmall<N, L>;
+
+    #[inline]
+    fn vec_buffer_try_new_in(
+        self,
+        capacity: <Self::Config as VecConfig>::Index,
+        exact: bool,
+    ) -> Result<<Self::Config as VecConfig>::Buffer<T>, StorageError> {
+        InlineBuffer::try_for_capacity(capacity, exact)
+    }
+}
+
 impl<T> VecNewIn<T> for Thin {
     type Config = Thin;
 
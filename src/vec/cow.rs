@@ -18,6 +18,21 @@ impl<T: Clone, A: RawAlloc> ToOwnedIn<A> for [T] {
     }
 }
 
+impl<'a, T: Clone, A: RawAlloc> Cow<'a, [T], A> {
+    /// Create an owned instance from a `Vec`.
+    #[inline]
+    pub fn from_vec(vec: Vec<T, A>) -> Self {
+        Cow::Owned(vec)
+    }
+
+    /// Create a borrowed instance from a slice reference. If converted into an
+    /// owned instance, the `Vec` is allocated using the allocator type `A`.
+    #[inline]
+    pub const fn borrowed(slice: &'a [T]) -> Self {
+        Cow::Borrowed(slice)
+    }
+}
+
 impl<'a, T: Clone, A: RawAlloc, const N: usize> From<&'a [T; N]> for Cow<'a, [T], A> {
     fn from(s: &'a [T; N]) -> Cow<'a, [T], A> {
         Cow::Borrowed(s.as_slice())
@@ -72,6 +87,32 @@ mod tests {
         assert_eq!(b.into_owned(), &[1, 2, 3]);
     }
 
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn cow_from_vec_borrowed() {
+        let b = Cow::<[u32], Global>::from_vec(Vec::from_slice(&[1, 2]));
+        assert!(b.is_owned());
+        assert_eq!(b.as_ref(), &[1, 2]);
+
+        let mut b = Cow::<[u32], Global>::borrowed(&[1, 2]);
+        assert!(b.is_borrowed());
+        let owned: &mut Vec<u32, Global> = b.to_mut();
+        owned.push(3);
+        assert!(b.is_owned());
+        assert_eq!(b.as_ref(), &[1, 2, 3]);
+    }
+
+    #[cfg(all(feature = "alloc", feature = "zeroize"))]
+    #[test]
+    fn cow_borrowed_custom_alloc() {
+        use crate::storage::ZeroizingAlloc;
+
+        let mut b = Cow::<[u32], ZeroizingAlloc<Global>>::borrowed(&[1, 2]);
+        let owned: &mut Vec<u32, ZeroizingAlloc<Global>> = b.to_mut();
+        owned.push(3);
+        assert_eq!(b.into_owned(), &[1, 2, 3]);
+    }
+
     #[cfg(feature = "alloc")]
     #[test]
     fn const_default_cow() {
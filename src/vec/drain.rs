@@ -5,17 +5,22 @@ use core::ops::Range;
 use core::ptr;
 use core::slice;
 
+use crate::error::StorageError;
 use crate::index::Index;
 
 use super::buffer::VecBuffer;
 use super::index_panic;
 
+/// A function used to shrink the buffer once a `Drain` is dropped.
+pub(super) type ShrinkFn<B> = fn(&mut B, <B as VecBuffer>::Index) -> Result<(), StorageError>;
+
 /// A struct used for draining items from a Vec as an iterator.
 pub struct Drain<'d, B: VecBuffer> {
     pub(super) range: Range<usize>,
     pub(super) remain: Range<usize>,
     pub(super) tail_length: usize,
     pub(super) buf: &'d mut B,
+    pub(super) shrink: Option<ShrinkFn<B>>,
 }
 
 impl<'d, B: VecBuffer> Drain<'d, B> {
@@ -34,6 +39,7 @@ impl<'d, B: VecBuffer> Drain<'d, B> {
             remain: range,
             tail_length,
             buf,
+            shrink: None,
         }
     }
 
@@ -71,13 +77,15 @@ impl<'d, B: VecBuffer> Drain<'d, B> {
         let remain_len = self.len();
         if remain_len > 0 {
             unsafe {
-                ptr::drop_in_place(self.as_mut_slice().as_mut_ptr());
+                ptr::drop_in_place(self.as_mut_slice());
             }
             self.remain.start = self.remain.end;
         }
     }
 
     /// Abort the drain operation, leaving the remaining items contained in the `Vec` instance.
+    ///
+    /// Items which have already been yielded by the iterator remain removed.
     pub fn keep_rest(mut self) {
         let len = self.len();
         let shift = self.remain.start - self.range.start;
@@ -177,6 +185,14 @@ impl<'d, B: VecBuffer> Drop for Drain<'d, B> {
                 self.buf
                     .set_length(B::Index::from_usize(self.range.start + self.tail_length))
             }
+        } else {
+            self.buf.vec_cleared();
+        }
+        if let Some(shrink) = self.shrink {
+            if len <= self.buf.capacity().to_usize() / 4 {
+                // a failure to shrink the buffer is not considered an error
+                let _ = shrink(self.buf, B::Index::from_usize(len));
+            }
         }
     }
 }
@@ -0,0 +1,85 @@
+use core::fmt;
+use core::fmt::Debug;
+
+use crate::index::Index;
+
+use super::config::VecConfig;
+use super::Vec;
+
+/// A view into a position of a sorted `Vec`, which may either be occupied
+/// by a matching item or vacant.
+///
+/// This is constructed by the [`Vec::entry`] family of methods.
+pub enum Entry<'a, T, C: VecConfig> {
+    /// An existing item matching the search.
+    Occupied(&'a mut T),
+    /// A position at which a matching item may be inserted.
+    Vacant(VacantEntry<'a, T, C>),
+}
+
+impl<'a, T, C: VecConfig> Entry<'a, T, C> {
+    /// Insert `value` if the entry is vacant, returning a mutable reference to
+    /// the item at the entry position.
+    ///
+    /// This method will panic on any storage errors.
+    #[inline]
+    pub fn or_insert(self, value: T) -> &'a mut T {
+        match self {
+            Self::Occupied(item) => item,
+            Self::Vacant(entry) => entry.insert(value),
+        }
+    }
+
+    /// Insert the result of `f` if the entry is vacant, returning a mutable reference
+    /// to the item at the entry position.
+    ///
+    /// This method will panic on any storage errors.
+    #[inline]
+    pub fn or_insert_with<F>(self, f: F) -> &'a mut T
+    where
+        F: FnOnce() -> T,
+    {
+        match self {
+            Self::Occupied(item) => item,
+            Self::Vacant(entry) => entry.insert(f()),
+        }
+    }
+}
+
+impl<'a, T: Debug, C: VecConfig> Debug for Entry<'a, T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Occupied(item) => f.debug_tuple("Occupied").field(item).finish(),
+            Self::Vacant(entry) => f.debug_tuple("Vacant").field(entry).finish(),
+        }
+    }
+}
+
+/// A vacant position within a sorted `Vec`.
+pub struct VacantEntry<'a, T, C: VecConfig> {
+    pub(super) vec: &'a mut Vec<T, C>,
+    pub(super) index: C::Index,
+}
+
+impl<'a, T, C: VecConfig> VacantEntry<'a, T, C> {
+    /// Get the index at which a new item would be inserted.
+    #[inline]
+    pub fn index(&self) -> C::Index {
+        self.index
+    }
+
+    /// Insert `value` at the vacant position, shifting all following items to
+    /// the right, and return a mutable reference to the inserted item.
+    ///
+    /// This method will panic on any storage errors.
+    pub fn insert(self, value: T) -> &'a mut T {
+        self.vec.insert(self.index, value);
+        &mut self.vec.as_mut_slice()[self.index.to_usize()]
+    }
+}
+
+impl<'a, T, C: VecConfig> Debug for VacantEntry<'a, T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("VacantEntry").field(&self.index).finish()
+    }
+}
@@ -0,0 +1,99 @@
+use core::fmt;
+use core::iter::FusedIterator;
+use core::ptr;
+
+use crate::index::Index;
+
+use super::buffer::VecBuffer;
+
+/// An iterator which removes and yields the items of a `Vec` matching a predicate.
+///
+/// Items which do not match the predicate are retained, and are compacted in
+/// place as the iterator advances. If the iterator is dropped before it is
+/// exhausted, any items which have not been examined remain in the `Vec`.
+pub struct ExtractIf<'e, B: VecBuffer, F> {
+    buf: &'e mut B,
+    index: usize,
+    removed: usize,
+    orig_len: usize,
+    pred: F,
+}
+
+impl<'e, B: VecBuffer, F> ExtractIf<'e, B, F> {
+    pub(super) fn new(buf: &'e mut B, pred: F) -> Self {
+        let orig_len = buf.length().to_usize();
+        if orig_len > 0 {
+            // SAFETY: buffer capacity is established as > 0
+            unsafe { buf.set_length(B::Index::ZERO) };
+        }
+        Self {
+            buf,
+            index: 0,
+            removed: 0,
+            orig_len,
+            pred,
+        }
+    }
+}
+
+impl<'e, B: VecBuffer, F> fmt::Debug for ExtractIf<'e, B, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExtractIf")
+            .field("index", &self.index)
+            .field("removed", &self.removed)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'e, B: VecBuffer, F> Iterator for ExtractIf<'e, B, F>
+where
+    F: FnMut(&mut B::Item) -> bool,
+{
+    type Item = B::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index < self.orig_len {
+            unsafe {
+                let read = self.buf.data_ptr_mut().add(self.index);
+                let matched = (self.pred)(&mut *read);
+                self.index += 1;
+                if matched {
+                    self.removed += 1;
+                    return Some(ptr::read(read));
+                } else if self.removed > 0 {
+                    ptr::copy_nonoverlapping(read, read.sub(self.removed), 1);
+                }
+            }
+        }
+        None
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.orig_len - self.index))
+    }
+}
+
+impl<'e, B: VecBuffer, F> FusedIterator for ExtractIf<'e, B, F> where F: FnMut(&mut B::Item) -> bool {}
+
+impl<'e, B: VecBuffer, F> Drop for ExtractIf<'e, B, F> {
+    fn drop(&mut self) {
+        let tail_length = self.orig_len - self.index;
+        if tail_length > 0 && self.removed > 0 {
+            unsafe {
+                let read = self.buf.data_ptr_mut().add(self.index);
+                ptr::copy(read, read.sub(self.removed), tail_length);
+            }
+        }
+        if self.orig_len > 0 {
+            // SAFETY: buffer capacity is established as > 0
+            unsafe {
+                self.buf
+                    .set_length(B::Index::from_usize(self.orig_len - self.removed))
+            };
+        }
+        if self.orig_len == self.removed {
+            self.buf.vec_cleared();
+        }
+    }
+}
@@ -95,10 +95,11 @@ impl<T> Drop for Inserter<'_, T> {
                 )
             };
         }
-        if self.tail > 0 {
+        if self.tail > 0 && self.start != self.buf.len() {
+            // move the tail items to follow the inserted items
             let range = self.buf.as_mut_ptr_range();
             unsafe {
-                ptr::copy(range.end, range.start, self.tail);
+                ptr::copy(range.end, range.start.add(self.start), self.tail);
             }
         }
     }
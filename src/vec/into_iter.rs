@@ -1,11 +1,13 @@
 use core::iter::FusedIterator;
+use core::mem::ManuallyDrop;
 use core::ops::Range;
 use core::ptr;
 use core::slice;
 
 use crate::index::Index;
 
-use super::buffer::VecBuffer;
+use super::buffer::{VecBuffer, VecBufferSpawn};
+use super::insert::Inserter;
 
 /// A struct used for extracting all items from a Vec as an iterator.
 #[derive(Debug)]
@@ -57,18 +59,61 @@ impl<B: VecBuffer> IntoIter<B> {
         self.remain.end - self.remain.start
     }
 
+    /// Move the remaining items to the front of the buffer and return it.
+    pub(super) fn into_buffer(self) -> B {
+        let mut me = ManuallyDrop::new(self);
+        let len = me.len();
+        if len > 0 {
+            let start = me.remain.start;
+            unsafe {
+                if start > 0 {
+                    let head = me.buf.data_ptr_mut();
+                    ptr::copy(head.add(start), head, len);
+                }
+                // SAFETY: buffer capacity is established as > 0
+                me.buf.set_length(B::Index::from_usize(len));
+            }
+        }
+        unsafe { ptr::read(&me.buf) }
+    }
+
     /// Drop any remaining items and set the remaining item count to zero.
     fn clear(&mut self) {
         let remain_len = self.len();
         if remain_len > 0 {
             unsafe {
-                ptr::drop_in_place(self.as_mut_slice().as_mut_ptr());
+                ptr::drop_in_place(self.as_mut_slice());
             }
             self.remain.start = self.remain.end;
         }
     }
 }
 
+impl<B: VecBufferSpawn> Clone for IntoIter<B>
+where
+    B::Item: Clone,
+{
+    fn clone(&self) -> Self {
+        let mut buf = match self
+            .buf
+            .vec_buffer_try_spawn(B::Index::from_usize(self.len()), false)
+        {
+            Ok(buf) => buf,
+            Err(err) => err.panic(),
+        };
+        let mut insert = Inserter::for_buffer(&mut buf);
+        for item in self.as_slice() {
+            insert.push_clone(item);
+        }
+        let (added, _) = insert.complete();
+        if added > 0 {
+            // SAFETY: buffer capacity is established as > 0
+            unsafe { buf.set_length(B::Index::from_usize(added)) };
+        }
+        Self::new(buf)
+    }
+}
+
 impl<B: VecBuffer> AsRef<[B::Item]> for IntoIter<B> {
     fn as_ref(&self) -> &[B::Item] {
         self.as_slice()
@@ -88,6 +88,28 @@
 //! let v = vec![in Inline::<5>; 1, 2, 3, 4, 5];
 //! ```
 //!
+//! Inline vectors may be constructed in `const` and `static` items:
+//!
+//! ```
+//! use flex_alloc::vec::InlineVec;
+//!
+//! static TABLE: InlineVec<u32, 8> = InlineVec::new();
+//! const PRIMES: InlineVec<u32, 4> = InlineVec::from_full_array([2, 3, 5, 7]);
+//!
+//! assert!(TABLE.is_empty());
+//! assert_eq!(PRIMES.len(), 4);
+//! ```
+//!
+//! For small capacities, [`InlineSmallVec`] stores the length of the vector
+//! in a compact index type (`u8` by default), reducing the size of the handle:
+//!
+//! ```
+//! use flex_alloc::vec::InlineSmallVec;
+//!
+//! let v = InlineSmallVec::<u8, 7>::from_slice(b"flex");
+//! assert_eq!(core::mem::size_of_val(&v), 8);
+//! ```
+//!
 //! ### Thin vectors
 //!
 //! Like the `thin-vec` crate (but without compatibility with Gecko), vectors
@@ -109,7 +131,7 @@
 //!
 //! [`Vec`] may be parameterized to use an alternative index type when memory
 //! consumption is a concern. The supported index types are `u8`, `u16`,
-//! `u32`, and `usize` (the default).
+//! `u32`, `u64`, and `usize` (the default).
 //!
 //! ```
 //! # #[cfg(feature = "alloc")] {
@@ -123,28 +145,35 @@ use core::borrow::{Borrow, BorrowMut};
 use core::cmp::Ordering;
 use core::fmt;
 use core::iter::repeat;
-use core::mem::{self, ManuallyDrop, MaybeUninit};
+use core::mem::{self, size_of, ManuallyDrop, MaybeUninit};
 use core::ops::{Bound, Deref, DerefMut, Range, RangeBounds};
-use core::ptr;
+use core::ptr::{self, NonNull};
 use core::slice;
 
-#[cfg(feature = "alloc")]
-use core::ptr::NonNull;
-
 use const_default::ConstDefault;
 
 use crate::error::{StorageError, UpdateError};
 use crate::index::{Grow, Index};
-use crate::storage::{Global, RawBuffer};
+use crate::storage::{
+    ArrayStorage, FixedAlloc, Global, Inline, InlineBuffer, RawAlloc, RawBuffer, SpillAlloc,
+};
 
-use self::buffer::VecBuffer;
+use self::buffer::{vec_buffer_try_shrink, VecBuffer};
 use self::config::{VecConfig, VecConfigAlloc, VecConfigNew, VecConfigSpawn, VecNewIn};
 use self::insert::Inserter;
 
 #[cfg(feature = "alloc")]
-use self::config::VecConfigAllocParts;
-
-pub use self::{drain::Drain, into_iter::IntoIter, splice::Splice};
+use self::{buffer::VecBufferBoxed, config::VecConfigAllocParts};
+
+pub use self::{
+    drain::Drain,
+    entry::{Entry, VacantEntry},
+    extract_if::ExtractIf,
+    into_iter::IntoIter,
+    pod::Pod,
+    splice::Splice,
+    writer::VecWriter,
+};
 
 pub mod buffer;
 pub mod config;
@@ -154,13 +183,26 @@ mod macros;
 
 mod cow;
 mod drain;
+mod entry;
+mod extract_if;
 pub(crate) mod insert;
 mod into_iter;
+mod pod;
+#[cfg(feature = "serde")]
+mod serialize;
 mod splice;
+mod writer;
 
 /// A vector which stores its contained data inline, using no external allocation.
 pub type InlineVec<T, const N: usize> = Vec<T, crate::storage::Inline<N>>;
 
+/// A vector which stores its contained data inline, using no external allocation,
+/// and records its length using the compact index type `L`.
+pub type InlineSmallVec<T, const N: usize, L = u8> = Vec<T, crate::storage::InlineSmall<N, L>>;
+
+/// A vector which stores its contained data in a fixed, borrowed buffer.
+pub type FixedVec<'a, T> = Vec<T, FixedAlloc<'a>>;
+
 #[cfg(feature = "alloc")]
 /// A vector which is pointer-sized, storing its capacity and length in the
 /// allocated buffer.
@@ -170,12 +212,26 @@ pub type ThinVec<T> = Vec<T, crate::storage::Thin>;
 /// A vector which automatically zeroizes its buffer when dropped.
 pub type ZeroizingVec<T> = Vec<T, crate::storage::ZeroizingAlloc<Global>>;
 
+#[cfg(feature = "zeroize")]
+/// A vector which automatically zeroizes its buffer when dropped, using the
+/// allocator `A` and the index type `I`.
+pub type ZeroizingVecIn<T, A = Global, I = usize> =
+    Vec<T, config::Custom<crate::storage::ZeroizingAlloc<A>, I>>;
+
 #[cold]
 #[inline(never)]
 pub(super) fn index_panic() -> ! {
     panic!("Invalid element index");
 }
 
+#[cold]
+fn index_overflow<I: Index>(requested: usize) -> StorageError {
+    StorageError::IndexOverflow {
+        requested,
+        max: I::MAX_USIZE,
+    }
+}
+
 #[inline]
 fn bounds_to_range<I: Index>(range: impl RangeBounds<I>, length: I) -> Range<usize> {
     let start = match range.start_bound() {
@@ -284,6 +340,9 @@ impl<T, C: VecConfigNew<T>> Vec<T, C> {
 
     /// Try to construct a new `Vec<T, C>` with a minimum capacity.
     pub fn try_with_capacity(capacity: C::Index) -> Result<Self, StorageError> {
+        if capacity.to_usize() > C::Grow::MAX_CAPACITY {
+            return Err(StorageError::CapacityLimit);
+        }
         let buffer = C::vec_buffer_try_new(capacity, false)?;
         Ok(Self { buffer })
     }
@@ -319,12 +378,82 @@ impl<T, C: VecConfigNew<T>> Vec<T, C> {
         T: Clone,
     {
         let Some(len) = C::Index::try_from_usize(data.len()) else {
-            return Err(StorageError::CapacityLimit);
+            return Err(index_overflow::<C::Index>(data.len()));
         };
         let mut vec = Self::try_with_capacity(len)?;
         vec.extend_from_slice(data);
         Ok(vec)
     }
+
+    /// Try to construct a new `Vec<T, C>` from the items of an iterator.
+    ///
+    /// If storage cannot be allocated for all of the items, then the error
+    /// is returned along with the partially-constructed vector, the item which
+    /// could not be stored, and the iterator producing any remaining items, so
+    /// that no items are lost.
+    pub fn try_from_iter<I>(iter: I) -> Result<Self, UpdateError<(Self, T, I::IntoIter)>>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        let (min_cap, _) = iter.size_hint();
+        // a failure to reserve the initial capacity is reported once the
+        // available storage is exhausted
+        let mut vec = C::Index::try_from_usize(min_cap)
+            .and_then(|cap| Self::try_with_capacity(cap).ok())
+            .unwrap_or_else(Self::new);
+        match vec.try_extend(&mut iter) {
+            Ok(_) => Ok(vec),
+            Err(err) => Err(UpdateError::new(err.error, (vec, err.value, iter))),
+        }
+    }
+
+    /// Construct a new `Vec<T, C>` by copying the items represented by the byte
+    /// slice `data`, which is not required to be aligned. Returns `None` if the
+    /// length of `data` is not a multiple of the size of `T`, or if `T` is a
+    /// zero-sized type and `data` is not empty.
+    ///
+    /// This method will panic on any storage errors.
+    pub fn from_bytes(data: &[u8]) -> Option<Self>
+    where
+        T: Pod,
+    {
+        match Self::try_from_bytes(data) {
+            Ok(res) => res,
+            Err(error) => error.panic(),
+        }
+    }
+
+    /// Try to construct a new `Vec<T, C>` by copying the items represented by the
+    /// byte slice `data`, which is not required to be aligned. Returns `Ok(None)`
+    /// if the length of `data` is not a multiple of the size of `T`, or if `T` is
+    /// a zero-sized type and `data` is not empty.
+    pub fn try_from_bytes(data: &[u8]) -> Result<Option<Self>, StorageError>
+    where
+        T: Pod,
+    {
+        let item_size = size_of::<T>();
+        let len = match data.len().checked_div(item_size) {
+            Some(len) if len * item_size == data.len() => len,
+            None if data.is_empty() => 0,
+            _ => return Ok(None),
+        };
+        let Some(index) = C::Index::try_from_usize(len) else {
+            return Err(index_overflow::<C::Index>(len));
+        };
+        let mut vec = Self::try_with_capacity(index)?;
+        if len > 0 {
+            unsafe {
+                ptr::copy_nonoverlapping(
+                    data.as_ptr(),
+                    vec.buffer.data_ptr_mut().cast::<u8>(),
+                    data.len(),
+                );
+                vec.buffer.set_length(index);
+            }
+        }
+        Ok(Some(vec))
+    }
 }
 
 impl<T, C: VecConfig> Vec<T, C> {
@@ -371,11 +500,59 @@ impl<T, C: VecConfig> Vec<T, C> {
     where
         A: VecNewIn<T, Config = C>,
     {
+        if capacity.to_usize() > C::Grow::MAX_CAPACITY {
+            return Err(StorageError::CapacityLimit);
+        }
         Ok(Self {
             buffer: A::vec_buffer_try_new_in(alloc_in, capacity, false)?,
         })
     }
 
+    /// Construct a new, empty `Vec<T, C>` using the allocator instance `alloc`
+    /// with a minimum initial capacity `capacity`, writing to each memory page
+    /// of the allocated capacity so that it is committed by the operating system
+    /// in advance of its use. The length of the vector is not affected.
+    ///
+    /// Only allocator instances are accepted, as fixed storage buffers do not
+    /// benefit from pre-faulting. See [`try_with_capacity_in_touched`] for a
+    /// description of the memory written.
+    ///
+    /// This method will panic on any storage errors.
+    ///
+    /// [`try_with_capacity_in_touched`]: Self::try_with_capacity_in_touched
+    #[cfg(all(feature = "std", unix))]
+    pub fn with_capacity_in_touched<A>(capacity: C::Index, alloc: A) -> Self
+    where
+        A: RawAlloc + VecNewIn<T, Config = C>,
+    {
+        match Self::try_with_capacity_in_touched(capacity, alloc) {
+            Ok(res) => res,
+            Err(error) => error.panic(),
+        }
+    }
+
+    /// Try to construct a new, empty `Vec<T, C>` using the allocator instance
+    /// `alloc` with a minimum initial capacity `capacity`, writing to each memory
+    /// page of the allocated capacity so that it is committed by the operating
+    /// system in advance of its use. The length of the vector is not affected.
+    ///
+    /// A zero byte is written at a stride of 4096 bytes through the spare
+    /// capacity, along with its final byte. This is the smallest page size in
+    /// common use: on targets with larger pages every page is still touched,
+    /// although some of the writes are redundant.
+    #[cfg(all(feature = "std", unix))]
+    pub fn try_with_capacity_in_touched<A>(
+        capacity: C::Index,
+        alloc: A,
+    ) -> Result<Self, StorageError>
+    where
+        A: RawAlloc + VecNewIn<T, Config = C>,
+    {
+        let mut vec = Self::try_with_capacity_in(capacity, alloc)?;
+        vec.touch_spare_capacity();
+        Ok(vec)
+    }
+
     /// Construct a new `Vec<T, C>` in the allocation provider `alloc_in`
     /// and extend it by cloning the slice `data`.
     ///
@@ -401,7 +578,7 @@ impl<T, C: VecConfig> Vec<T, C> {
         A: VecNewIn<T, Config = C>,
     {
         let Some(len) = C::Index::try_from_usize(data.len()) else {
-            return Err(StorageError::CapacityLimit);
+            return Err(index_overflow::<C::Index>(data.len()));
         };
         let mut vec = Self::try_with_capacity_in(len, alloc_in)?;
         vec.extend_from_slice(data);
@@ -415,6 +592,154 @@ impl<T, C: VecConfig> Vec<T, C> {
         let me = ManuallyDrop::new(self);
         unsafe { ptr::read(&me.buffer) }
     }
+
+    #[cfg(all(feature = "std", unix))]
+    fn touch_spare_capacity(&mut self) {
+        // a stride hint rather than the page size reported by the OS
+        const PAGE_STRIDE: usize = 4096;
+
+        let spare = self.spare_capacity_mut();
+        let size = mem::size_of_val(spare);
+        if size == 0 {
+            return;
+        }
+        let start = spare.as_mut_ptr().cast::<MaybeUninit<u8>>();
+        let mut offset = 0;
+        while offset < size {
+            // SAFETY: the offset is within the spare capacity of the buffer
+            unsafe { ptr::write_volatile(start.add(offset), MaybeUninit::new(0)) };
+            offset += PAGE_STRIDE;
+        }
+        // the final page may not be reached if the buffer is not page-aligned
+        // SAFETY: `size` is non-zero, so the final byte is within the spare capacity
+        unsafe { ptr::write_volatile(start.add(size - 1), MaybeUninit::new(0)) };
+    }
+}
+
+impl<T, C: VecConfigNew<T>> Vec<Vec<T, C>, C> {
+    /// Flatten this vector of vectors into a single vector, cloning each of
+    /// the contained items.
+    ///
+    /// This method will panic on any storage errors.
+    pub fn concat_vec(&self) -> Vec<T, C>
+    where
+        T: Clone,
+    {
+        let total = self
+            .iter()
+            .try_fold(0usize, |total, v| total.checked_add(v.len().to_usize()));
+        let mut result = Vec::new();
+        match result._try_reserve(total.unwrap_or(usize::MAX), true) {
+            Ok(_) => (),
+            Err(error) => error.panic(),
+        }
+        for v in self.iter() {
+            unsafe { result.extend_unchecked(v) };
+        }
+        result
+    }
+
+    /// Flatten this vector of vectors into a single vector, cloning each of
+    /// the contained items and placing a copy of `sep` between each vector.
+    ///
+    /// This method will panic on any storage errors.
+    pub fn join_vec(&self, sep: &T) -> Vec<T, C>
+    where
+        T: Clone,
+    {
+        let total = self.iter().try_fold(0usize, |total, v| {
+            total.checked_add(v.len().to_usize())?.checked_add(1)
+        });
+        let mut result = Vec::new();
+        match result._try_reserve(total.map_or(usize::MAX, |t| t.saturating_sub(1)), true) {
+            Ok(_) => (),
+            Err(error) => error.panic(),
+        }
+        for (idx, v) in self.iter().enumerate() {
+            if idx > 0 {
+                unsafe { result.push_unchecked(sep.clone()) };
+            }
+            unsafe { result.extend_unchecked(v) };
+        }
+        result
+    }
+}
+
+impl<T, const N: usize> Vec<T, Inline<N>> {
+    /// Create a new inline vector from an array of `N` items, filling its
+    /// capacity.
+    pub const fn from_full_array(data: [T; N]) -> Self {
+        Self {
+            buffer: InlineBuffer {
+                storage: ArrayStorage::from_array(data),
+                length: N,
+            },
+        }
+    }
+
+    /// Create a new inline vector filling its capacity with the results of
+    /// calling `f` with each index in `0..N`, analogous to `core::array::from_fn`.
+    ///
+    /// If `f` panics, then any items already produced are dropped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use flex_alloc::vec::InlineVec;
+    ///
+    /// let squares = InlineVec::<usize, 5>::from_fn(|i| i * i);
+    /// assert_eq!(squares, &[0, 1, 4, 9, 16]);
+    /// ```
+    pub fn from_fn<F>(mut f: F) -> Self
+    where
+        F: FnMut(usize) -> T,
+    {
+        let mut vec = Self::new();
+        for index in 0..N {
+            // SAFETY: the capacity of the inline buffer is `N`
+            unsafe { vec.push_unchecked(f(index)) };
+        }
+        vec
+    }
+}
+
+impl<'a, T> Vec<T, FixedAlloc<'a>> {
+    /// Create a new, empty `Vec` backed by a fixed storage buffer. The capacity of
+    /// the vector is equal to the length of `storage`.
+    pub fn from_fixed_storage(storage: &'a mut [MaybeUninit<T>]) -> Self {
+        let capacity = storage.len();
+        let data = unsafe { NonNull::new_unchecked(storage.as_mut_ptr()) }.cast();
+        Self {
+            buffer: <FixedAlloc<'a> as config::VecConfigAllocParts<T>>::vec_buffer_from_parts(
+                data,
+                0,
+                capacity,
+                FixedAlloc::default(),
+            ),
+        }
+    }
+
+    /// Drop the contained items and release the fixed storage backing this vector,
+    /// allowing it to be reused. The returned slice has a length equal to the
+    /// capacity of the vector.
+    pub fn into_fixed_storage(mut self) -> &'a mut [MaybeUninit<T>] {
+        self.clear();
+        let capacity = self.capacity();
+        let data = self.as_mut_ptr();
+        let _ = self.into_inner();
+        unsafe { slice::from_raw_parts_mut(data.cast(), capacity) }
+    }
+}
+
+impl<'a, T, A: RawAlloc> Vec<T, SpillAlloc<'a, A>> {
+    /// Determine whether the contents of this vector have spilled over from
+    /// the initial fixed storage buffer into a dynamic allocation.
+    pub fn spilled(&self) -> bool {
+        self.buffer.capacity() > 0
+            && !self
+                .allocator()
+                .is_fixed_storage(self.buffer.data_ptr().cast())
+    }
 }
 
 impl<T, C: VecConfigAlloc<T>> Vec<T, C> {
@@ -427,31 +752,29 @@ impl<T, C: VecConfigAlloc<T>> Vec<T, C> {
 #[cfg(feature = "alloc")]
 impl<T, C> Vec<T, C>
 where
-    C: VecConfigAllocParts<T, Alloc = Global, Index = usize>,
+    C: VecConfig<Index = usize>,
+    C::Buffer<T>: VecBufferBoxed,
 {
     /// Convert this instance into a `Box<[T]>`. This may produce a new allocation
-    /// if the length of the collection does not match its capacity.
+    /// if the length of the collection does not match its capacity. As a thin vector
+    /// stores its header within its allocation, converting a [`ThinVec`] always
+    /// moves the items into a new allocation.
     ///
     /// This method will panic on any storage errors.
-    pub fn into_boxed_slice(mut self) -> alloc::boxed::Box<[T]> {
-        self.shrink_to_fit();
-        let (data, length, capacity, _alloc) = self.into_parts();
-        assert_eq!(capacity, length, "length-capacity mismatch");
-        let data = ptr::slice_from_raw_parts_mut(data.as_ptr(), length);
-        unsafe { alloc::boxed::Box::from_raw(data) }
+    pub fn into_boxed_slice(self) -> alloc::boxed::Box<[T]> {
+        match self.try_into_boxed_slice() {
+            Ok(boxed) => boxed,
+            Err(err) => err.error.panic(),
+        }
     }
 
     /// Try to convert this instance into a `Box<[T]>`. This may produce a new allocation
-    /// if the length of the collection does not match its capacity.
-    pub fn try_into_boxed_slice(mut self) -> Result<alloc::boxed::Box<[T]>, UpdateError<Self>> {
-        match self.try_shrink_to_fit() {
-            Ok(()) => (),
-            Err(e) => return Err(UpdateError::new(e, self)),
-        }
-        let (data, length, capacity, _alloc) = self.into_parts();
-        assert_eq!(capacity, length, "length-capacity mismatch");
-        let data = ptr::slice_from_raw_parts_mut(data.as_ptr(), length);
-        Ok(unsafe { alloc::boxed::Box::from_raw(data) })
+    /// if the length of the collection does not match its capacity. As a thin vector
+    /// stores its header within its allocation, converting a [`ThinVec`] always
+    /// moves the items into a new allocation.
+    pub fn try_into_boxed_slice(self) -> Result<alloc::boxed::Box<[T]>, UpdateError<Self>> {
+        C::Buffer::<T>::vec_buffer_try_into_boxed(self.into_inner())
+            .map_err(|(err, buffer)| UpdateError::new(err, Self { buffer }))
     }
 }
 
@@ -490,6 +813,18 @@ impl<T, C: VecConfig> Vec<T, C> {
         self.buffer.data_ptr_mut()
     }
 
+    /// Get a non-null pointer to the beginning of the data allocation. The items of
+    /// the vector are always stored contiguously starting at this pointer.
+    ///
+    /// The pointer is never null, but it is dangling (well-aligned, but not valid
+    /// for reads or writes) if `T` is zero sized or the current capacity is zero.
+    #[inline]
+    pub fn as_non_null(&mut self) -> NonNull<T> {
+        // SAFETY: all buffer implementations use a dangling pointer rather than
+        // a null pointer when no allocation is present
+        unsafe { NonNull::new_unchecked(self.buffer.data_ptr_mut()) }
+    }
+
     /// Access the contained data as a slice reference.
     #[inline]
     pub fn as_slice(&self) -> &[T] {
@@ -502,6 +837,61 @@ impl<T, C: VecConfig> Vec<T, C> {
         self.buffer.as_mut_slice()
     }
 
+    /// Access the contained data as a slice of bytes.
+    pub fn as_bytes(&self) -> &[u8]
+    where
+        T: Pod,
+    {
+        let slice = self.as_slice();
+        unsafe { slice::from_raw_parts(slice.as_ptr().cast(), mem::size_of_val(slice)) }
+    }
+
+    /// Access the first `N` items of the vector as an array reference, or return
+    /// `None` if the length of the vector is less than `N`.
+    pub fn first_chunk<const N: usize>(&self) -> Option<&[T; N]> {
+        if self.buffer.length().to_usize() < N {
+            None
+        } else {
+            Some(unsafe { &*self.buffer.data_ptr().cast::<[T; N]>() })
+        }
+    }
+
+    /// Access the first `N` items of the vector as a mutable array reference, or
+    /// return `None` if the length of the vector is less than `N`.
+    pub fn first_chunk_mut<const N: usize>(&mut self) -> Option<&mut [T; N]> {
+        if self.buffer.length().to_usize() < N {
+            None
+        } else {
+            Some(unsafe { &mut *self.buffer.data_ptr_mut().cast::<[T; N]>() })
+        }
+    }
+
+    /// Access the contained items as a slice of `N`-element arrays, followed by
+    /// a slice of the remaining `len % N` items.
+    ///
+    /// Panics if `N` is zero.
+    pub fn as_chunks<const N: usize>(&self) -> (&[[T; N]], &[T]) {
+        assert!(N != 0, "chunk size must be non-zero");
+        let len = self.buffer.length().to_usize();
+        let split = len - len % N;
+        let (head, tail) = self.as_slice().split_at(split);
+        let chunks = unsafe { slice::from_raw_parts(head.as_ptr().cast(), split / N) };
+        (chunks, tail)
+    }
+
+    /// Access the contained items as a mutable slice of `N`-element arrays, followed
+    /// by a mutable slice of the remaining `len % N` items.
+    ///
+    /// Panics if `N` is zero.
+    pub fn as_chunks_mut<const N: usize>(&mut self) -> (&mut [[T; N]], &mut [T]) {
+        assert!(N != 0, "chunk size must be non-zero");
+        let len = self.buffer.length().to_usize();
+        let split = len - len % N;
+        let (head, tail) = self.as_mut_slice().split_at_mut(split);
+        let chunks = unsafe { slice::from_raw_parts_mut(head.as_mut_ptr().cast(), split / N) };
+        (chunks, tail)
+    }
+
     /// Get the current capacity of the collection. This represents the number
     /// of items which can be contained without creating a new allocation.
     #[inline]
@@ -509,18 +899,99 @@ impl<T, C: VecConfig> Vec<T, C> {
         self.buffer.capacity()
     }
 
+    /// Get the number of additional items which can be added to the collection
+    /// without creating a new allocation.
+    #[inline]
+    pub fn capacity_remaining(&self) -> C::Index {
+        C::Index::from_usize(self.buffer.capacity().to_usize() - self.buffer.length().to_usize())
+    }
+
+    /// Iterate over non-overlapping mutable chunks of the vector, each containing
+    /// `size` items. If the length of the vector is not evenly divided by `size`,
+    /// then the final chunk contains the remaining items and is shorter than `size`.
+    ///
+    /// This method will panic if `size` is zero.
+    #[inline]
+    pub fn chunks_mut(&mut self, size: C::Index) -> slice::ChunksMut<'_, T> {
+        let size = size.to_usize();
+        assert!(size != 0, "chunk size must be non-zero");
+        self.as_mut_slice().chunks_mut(size)
+    }
+
     /// Clear the collection, dropping any contained items.
     #[inline]
     pub fn clear(&mut self) {
         self.truncate(C::Index::ZERO);
     }
 
+    /// Try to create a copy of this vector, cloning each of the contained items.
+    ///
+    /// Unlike `clone`, storage errors are returned rather than causing a panic.
+    pub fn try_clone(&self) -> Result<Self, StorageError>
+    where
+        T: Clone,
+        C: VecConfigSpawn<T>,
+    {
+        let mut inst = Self {
+            buffer: C::vec_buffer_try_spawn(&self.buffer, self.buffer.length(), false)?,
+        };
+        // SAFETY: the new buffer has sufficient capacity for the existing items
+        unsafe { inst.extend_unchecked(self) };
+        Ok(inst)
+    }
+
+    /// Replace the contents of this vector with clones of the items in `src`,
+    /// reusing the existing capacity. Unlike `clone_from`, the vector is never
+    /// grown: this method will panic if the length of `src` exceeds the current
+    /// capacity of the vector.
+    #[inline]
+    pub fn assign_from_slice(&mut self, src: &[T])
+    where
+        T: Clone,
+    {
+        match self.try_assign_from_slice(src) {
+            Ok(_) => (),
+            Err(error) => error.panic(),
+        }
+    }
+
+    /// Try to replace the contents of this vector with clones of the items in `src`,
+    /// reusing the existing capacity. Unlike `clone_from`, the vector is never
+    /// grown: if the length of `src` exceeds the current capacity of the vector,
+    /// then an error is returned and no changes are made to the vector.
+    pub fn try_assign_from_slice(&mut self, src: &[T]) -> Result<(), StorageError>
+    where
+        T: Clone,
+    {
+        if src.len() > self.buffer.capacity().to_usize() {
+            return Err(StorageError::CapacityLimit);
+        }
+        let len = self.buffer.length().to_usize();
+        if len > 0 {
+            // the items are dropped directly, as `truncate` may release the capacity
+            // SAFETY: buffer capacity is established as > 0
+            unsafe { self.buffer.set_length(C::Index::ZERO) };
+            let to_drop = ptr::slice_from_raw_parts_mut(self.buffer.data_ptr_mut(), len);
+            unsafe { ptr::drop_in_place(to_drop) };
+        }
+        unsafe { self.extend_unchecked(src) };
+        Ok(())
+    }
+
     /// Determine if the current collection length is zero.
     #[inline]
     pub fn is_empty(&self) -> bool {
         self.len() == C::Index::ZERO
     }
 
+    /// Determine if the current collection length is equal to its capacity,
+    /// such that no further items may be added without a new allocation.
+    /// See [`Vec::capacity_remaining`] for the number of items which may be added.
+    #[inline]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity()
+    }
+
     /// Consumes and leaks the vector, returning a mutable reference to the contents,
     /// `&'a mut [T]`. Note that the type `T` must outlive the chosen lifetime `'a`. If the
     /// type has only static references, or none at all, then this may be chosen to be
@@ -573,19 +1044,22 @@ impl<T, C: VecConfig> Vec<T, C> {
     /// Additional capacity may be allocated.
     #[inline]
     pub fn try_reserve(&mut self, reserve: C::Index) -> Result<(), StorageError> {
-        self._try_reserve(reserve.into(), false)
+        self._try_reserve(reserve.to_usize(), false)
     }
 
     fn _try_reserve(&mut self, reserve: usize, exact: bool) -> Result<(), StorageError> {
         let buf_cap: usize = self.buffer.capacity().to_usize();
         let Some(buf_needed) = self.buffer.length().to_usize().checked_add(reserve) else {
-            return Err(StorageError::CapacityLimit);
+            return Err(index_overflow::<C::Index>(usize::MAX));
         };
         if buf_cap >= buf_needed {
             return Ok(());
         }
-        let Some(mut capacity) = C::Index::try_from_usize(buf_needed) else {
+        if buf_needed > C::Grow::MAX_CAPACITY {
             return Err(StorageError::CapacityLimit);
+        }
+        let Some(mut capacity) = C::Index::try_from_usize(buf_needed) else {
+            return Err(index_overflow::<C::Index>(buf_needed));
         };
         if !exact {
             capacity = C::Grow::next_capacity::<T, _>(self.buffer.capacity(), capacity);
@@ -610,40 +1084,168 @@ impl<T, C: VecConfig> Vec<T, C> {
     /// new allocation will not exceed `reserve`.
     #[inline]
     pub fn try_reserve_exact(&mut self, reserve: C::Index) -> Result<(), StorageError> {
-        self._try_reserve(reserve.into(), true)
+        self._try_reserve(reserve.to_usize(), true)
+    }
+
+    /// Ensure that the collection has sufficient capacity for the items to be
+    /// produced by `iter`, based on its size hint. The upper bound is used when
+    /// available, otherwise the lower bound.
+    ///
+    /// This method will panic on any storage errors.
+    #[inline]
+    pub fn reserve_for_extend<I: Iterator>(&mut self, iter: &I) {
+        match self.try_reserve_for_extend(iter) {
+            Ok(_) => (),
+            Err(error) => error.panic(),
+        }
+    }
+
+    /// Try to ensure that the collection has sufficient capacity for the items to
+    /// be produced by `iter`, based on its size hint. The upper bound is used when
+    /// available, otherwise the lower bound.
+    pub fn try_reserve_for_extend<I: Iterator>(&mut self, iter: &I) -> Result<(), StorageError> {
+        let (min, max) = iter.size_hint();
+        self._try_reserve(max.unwrap_or(min), false)
+    }
+
+    /// Replace each item after the first with the result of `f(prev, cur)`, where
+    /// `prev` is the previously accumulated item and `cur` is the current item.
+    ///
+    /// This computes a prefix fold in place, for example a running sum when `f`
+    /// performs addition.
+    pub fn accumulate<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T, &T) -> T,
+    {
+        let data = self.as_mut_slice();
+        for idx in 1..data.len() {
+            let (head, tail) = data.split_at_mut(idx);
+            tail[0] = f(&head[idx - 1], &tail[0]);
+        }
     }
 
     /// Append the contents of another vector to this instance, removing
     /// the items from `other` in the process.
+    ///
+    /// This method will panic on any storage errors.
+    #[inline]
     pub fn append(&mut self, other: &mut Self) {
+        match self.try_append(other) {
+            Ok(_) => (),
+            Err(error) => error.panic(),
+        }
+    }
+
+    /// Try to append the contents of another vector to this instance, removing
+    /// the items from `other` in the process.
+    ///
+    /// Capacity is reserved up-front, so `other` is only drained on success. If
+    /// an `Err(StorageError)` is returned then both vectors are left unchanged.
+    pub fn try_append(&mut self, other: &mut Self) -> Result<(), StorageError> {
+        if other.is_empty() {
+            return Ok(());
+        }
         if self.is_empty() {
+            // exchanging the buffers is infallible, and any capacity held by
+            // this instance is retained by `other`
             mem::swap(&mut self.buffer, &mut other.buffer);
-        } else if !other.is_empty() {
-            let cur_len = self.buffer.length().to_usize();
-            let cp_len = other.len();
-            self.reserve(cp_len);
-            unsafe {
-                ptr::copy_nonoverlapping(
-                    other.buffer.data_ptr(),
-                    self.buffer.data_ptr_mut().add(cur_len),
-                    cp_len.to_usize(),
-                );
-            }
-            // SAFETY: capacity of both buffers has been established as > 0
-            unsafe { other.buffer.set_length(C::Index::ZERO) };
-            unsafe {
-                self.buffer
-                    .set_length(C::Index::from_usize(cur_len + cp_len.to_usize()))
-            };
+            return Ok(());
         }
+        let cur_len = self.buffer.length().to_usize();
+        let cp_len = other.buffer.length().to_usize();
+        self._try_reserve(cp_len, false)?;
+        unsafe {
+            ptr::copy_nonoverlapping(
+                other.buffer.data_ptr(),
+                self.buffer.data_ptr_mut().add(cur_len),
+                cp_len,
+            );
+        }
+        // SAFETY: capacity of both buffers has been established as > 0
+        unsafe { other.buffer.set_length(C::Index::ZERO) };
+        unsafe {
+            self.buffer
+                .set_length(C::Index::from_usize(cur_len + cp_len))
+        };
+        Ok(())
     }
 
-    /// Removes consecutive repeated elements in the vector according to the PartialEq
-    /// trait implementation.
+    /// Search a sorted vector for an item equal to `value`. If found, then the index
+    /// of the matching item is returned as `Ok`, otherwise the index at which the
+    /// item could be inserted while maintaining the sort order is returned as `Err`.
     ///
-    /// If the vector is sorted, this removes all duplicates.
+    /// If the vector is not sorted, the result is unspecified. If multiple items
+    /// match, then any one of them may be returned.
     #[inline]
-    pub fn dedup(&mut self)
+    pub fn binary_search(&self, value: &T) -> Result<C::Index, C::Index>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|item| item.cmp(value))
+    }
+
+    /// Search a sorted vector using a comparator function. See
+    /// [`binary_search`](Self::binary_search) for a description of the result.
+    ///
+    /// The comparator function should return an ordering indicating whether its
+    /// argument is `Less`, `Equal` or `Greater` than the desired target.
+    #[inline]
+    pub fn binary_search_by<'a, F>(&'a self, f: F) -> Result<C::Index, C::Index>
+    where
+        F: FnMut(&'a T) -> Ordering,
+    {
+        self.as_slice()
+            .binary_search_by(f)
+            .map(C::Index::from_usize)
+            .map_err(C::Index::from_usize)
+    }
+
+    /// Search a sorted vector using a key extraction function. See
+    /// [`binary_search`](Self::binary_search) for a description of the result.
+    #[inline]
+    pub fn binary_search_by_key<'a, B, F>(&'a self, b: &B, f: F) -> Result<C::Index, C::Index>
+    where
+        F: FnMut(&'a T) -> B,
+        B: Ord,
+    {
+        self.as_slice()
+            .binary_search_by_key(b, f)
+            .map(C::Index::from_usize)
+            .map_err(C::Index::from_usize)
+    }
+
+    /// Remove and drop the first `count` items of this vector, without constructing
+    /// an iterator. The remaining items are shifted to the front of the vector once.
+    /// If `count` is greater than the length of the vector, then all items are removed.
+    pub fn consume_prefix(&mut self, count: C::Index) {
+        let len = self.buffer.length().to_usize();
+        let count = count.to_usize().min(len);
+        if count == 0 {
+            return;
+        }
+        let remain = len - count;
+        // SAFETY: buffer capacity is established as > 0. Should dropping an item
+        // panic, the remaining items are leaked.
+        unsafe { self.buffer.set_length(C::Index::ZERO) };
+        let head = self.buffer.data_ptr_mut();
+        unsafe {
+            ptr::drop_in_place(ptr::slice_from_raw_parts_mut(head, count));
+            if remain > 0 {
+                ptr::copy(head.add(count), head, remain);
+            }
+            self.buffer.set_length(C::Index::from_usize(remain));
+        }
+        if remain == 0 {
+            self.buffer.vec_cleared();
+        }
+    }
+
+    /// Removes consecutive repeated elements in the vector according to the PartialEq
+    /// trait implementation.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    #[inline]
+    pub fn dedup(&mut self)
     where
         T: Eq,
     {
@@ -659,13 +1261,35 @@ impl<T, C: VecConfig> Vec<T, C> {
     /// removed.
     ///
     /// If the vector is sorted, this removes all duplicates.
-    pub fn dedup_by<F>(&mut self, mut cmp: F)
+    pub fn dedup_by<F>(&mut self, cmp: F)
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        self.dedup_by_count(cmp);
+    }
+
+    /// Removes consecutive repeated elements in the vector according to the
+    /// [`PartialEq`] trait implementation, returning the number of elements removed.
+    ///
+    /// If the vector is sorted, this removes all duplicates.
+    pub fn dedup_count(&mut self) -> C::Index
+    where
+        T: Eq,
+    {
+        self.dedup_by_count(|a, b| a == b)
+    }
+
+    /// Removes all but the first of consecutive elements in the vector satisfying a
+    /// given predicate, returning the number of elements removed.
+    ///
+    /// See [`Vec::dedup_by`] for a description of the `cmp` function.
+    pub fn dedup_by_count<F>(&mut self, mut cmp: F) -> C::Index
     where
         F: FnMut(&mut T, &mut T) -> bool,
     {
         let orig_len = self.buffer.length().to_usize();
         if orig_len < 2 {
-            return;
+            return C::Index::ZERO;
         }
         let mut new_len = 1;
         let mut head = self.as_mut_ptr();
@@ -687,7 +1311,8 @@ impl<T, C: VecConfig> Vec<T, C> {
             }
         }
         // SAFETY: capacity of the buffer has been established as > 0
-        unsafe { self.buffer.set_length(C::Index::from_usize(new_len)) }
+        unsafe { self.buffer.set_length(C::Index::from_usize(new_len)) };
+        C::Index::from_usize(orig_len - new_len)
     }
 
     /// Removes all but the first of consecutive elements in the vector that resolve to
@@ -703,6 +1328,51 @@ impl<T, C: VecConfig> Vec<T, C> {
         self.dedup_by(|a, b| key_f(a) == key_f(b))
     }
 
+    /// Removes each element of the vector which is equal to any of the preceding
+    /// `window` retained elements, according to the predicate `eq`.
+    ///
+    /// The predicate is passed a reference to the candidate element followed by a
+    /// reference to a previously retained element. A `window` of one is equivalent
+    /// to [`Vec::dedup_by`], while a `window` of zero retains all elements.
+    ///
+    /// For example, with a window of two the vector `[1, 2, 1, 3, 1]` becomes
+    /// `[1, 2, 3, 1]`: the second `1` is within two retained elements of the first,
+    /// while the final `1` is only compared against the retained `2` and `3`.
+    pub fn dedup_window<F>(&mut self, window: C::Index, mut eq: F)
+    where
+        F: FnMut(&T, &T) -> bool,
+    {
+        let window = window.to_usize();
+        let orig_len = self.buffer.length().to_usize();
+        if orig_len < 2 || window == 0 {
+            return;
+        }
+        // SAFETY: capacity of the buffer has been established as > 0
+        unsafe { self.buffer.set_length(C::Index::ZERO) };
+        let head = self.as_mut_ptr();
+        let mut len = 1;
+        let read_slice = DropSlice {
+            ptr: unsafe { head.add(1) },
+            len: orig_len - 1,
+        };
+        for read in read_slice {
+            let retained = unsafe {
+                slice::from_raw_parts(head.add(len.saturating_sub(window)), len.min(window))
+            };
+            let item = unsafe { &*read };
+            if retained.iter().any(|prev| eq(item, prev)) {
+                unsafe { ptr::drop_in_place(read) };
+            } else {
+                let tail = unsafe { head.add(len) };
+                if tail != read {
+                    unsafe { ptr::copy_nonoverlapping(read, tail, 1) };
+                }
+                len += 1;
+            }
+        }
+        unsafe { self.buffer.set_length(C::Index::from_usize(len)) };
+    }
+
     /// Extract a range of items from this vector, returning an iterator over
     /// the extracted items. If this iterator is dropped
     #[inline]
@@ -714,6 +1384,103 @@ impl<T, C: VecConfig> Vec<T, C> {
         Drain::new(&mut self.buffer, range)
     }
 
+    /// Shorten the vector to a length of `len`, returning an iterator over the
+    /// removed items. This is equivalent to `drain(len..)`, except that no items
+    /// are removed when `len` is greater than or equal to the current length.
+    /// The capacity of the vector is not affected.
+    #[inline]
+    pub fn split_truncate(&mut self, len: C::Index) -> Drain<'_, C::Buffer<T>> {
+        let length = self.buffer.length().to_usize();
+        let start = len.to_usize().min(length);
+        Drain::new(&mut self.buffer, start..length)
+    }
+
+    /// Extract a range of items from this vector, returning an iterator over
+    /// the extracted items. Once the iterator is dropped, the capacity of the
+    /// vector is shrunk to fit its remaining items if they occupy no more than
+    /// one quarter of the capacity, as with [`shrink_to_fit`](Self::shrink_to_fit).
+    ///
+    /// Errors encountered while shrinking the vector are ignored.
+    #[inline]
+    pub fn drain_and_shrink<R>(&mut self, range: R) -> Drain<'_, C::Buffer<T>>
+    where
+        R: RangeBounds<C::Index>,
+    {
+        let mut drain = self.drain(range);
+        drain.shrink = Some(vec_buffer_try_shrink::<_, C::Grow>);
+        drain
+    }
+
+    /// Extract the first `count` items of this vector, returning an iterator over
+    /// the extracted items. Once the iterator is dropped, the remaining items are
+    /// shifted to the front of the vector in a single move. If `count` is greater
+    /// than the length of the vector, then all items are extracted.
+    #[inline]
+    pub fn drain_prefix(&mut self, count: C::Index) -> Drain<'_, C::Buffer<T>> {
+        let count = count.to_usize().min(self.buffer.length().to_usize());
+        Drain::new(&mut self.buffer, 0..count)
+    }
+
+    /// Search a sorted vector for an item equal to `value`, returning an [`Entry`]
+    /// which either references the matching item or records the position at
+    /// which it may be inserted while maintaining the sort order.
+    ///
+    /// If the vector is not sorted, the result is unspecified. If multiple items
+    /// match, then any one of them may be returned.
+    #[inline]
+    pub fn entry(&mut self, value: &T) -> Entry<'_, T, C>
+    where
+        T: Ord,
+    {
+        self.entry_by(|item| item.cmp(value))
+    }
+
+    /// Search a sorted vector using a comparator function, returning an [`Entry`]
+    /// which either references the matching item or records the position at
+    /// which it may be inserted while maintaining the sort order.
+    ///
+    /// The comparator function should return an ordering indicating whether its
+    /// argument is `Less`, `Equal` or `Greater` than the desired target.
+    pub fn entry_by<F>(&mut self, f: F) -> Entry<'_, T, C>
+    where
+        F: FnMut(&T) -> Ordering,
+    {
+        match self.as_slice().binary_search_by(f) {
+            Ok(index) => Entry::Occupied(&mut self.as_mut_slice()[index]),
+            Err(index) => Entry::Vacant(VacantEntry {
+                vec: self,
+                index: C::Index::from_usize(index),
+            }),
+        }
+    }
+
+    /// Search a vector which is sorted by a key extraction function, returning an
+    /// [`Entry`] which either references the matching item or records the position
+    /// at which it may be inserted while maintaining the sort order.
+    ///
+    /// The key extraction function returns a reference to the key, which may be
+    /// borrowed from the item. Keys which are computed rather than borrowed may be
+    /// compared using [`entry_by`](Self::entry_by).
+    #[inline]
+    pub fn entry_by_key<K, F>(&mut self, key: &K, mut f: F) -> Entry<'_, T, C>
+    where
+        F: FnMut(&T) -> &K,
+        K: Ord + ?Sized,
+    {
+        self.entry_by(|item| f(item).cmp(key))
+    }
+
+    /// Access the contained items as a slice of `N`-element arrays, returning `None`
+    /// when the length of the vector is not an exact multiple of `N`.
+    ///
+    /// Panics if `N` is zero.
+    pub fn exact_chunks<const N: usize>(&self) -> Option<&[[T; N]]> {
+        match self.as_chunks::<N>() {
+            (chunks, []) => Some(chunks),
+            _ => None,
+        }
+    }
+
     /// Clone each entry in `items` and push it onto this vector.
     ///
     /// This method will panic on any storage errors.
@@ -792,6 +1559,144 @@ impl<T, C: VecConfig> Vec<T, C> {
         Ok(())
     }
 
+    /// Merge the items of a sorted iterator into this sorted vector, skipping any
+    /// items which are already present. If both the vector and the iterator are
+    /// sorted in ascending order, then the vector remains sorted and free of
+    /// duplicates.
+    ///
+    /// Incoming items are collected into the spare capacity of the vector and
+    /// merged from the back, so that existing items are moved at most once for
+    /// each batch of items. Capacity is reserved based on the size hint of the
+    /// iterator, and a batch may hold up to half of the spare capacity.
+    ///
+    /// If either the vector or the iterator is not sorted, the result is unspecified.
+    ///
+    /// This method will panic on any storage errors.
+    pub fn extend_sorted_unique<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+        T: Ord,
+    {
+        let mut iter = iter.into_iter();
+        let mut next = iter.next();
+        while next.is_some() {
+            let len = self.buffer.length().to_usize();
+            if self.buffer.capacity().to_usize() - len < 2 {
+                let reserve = iter.size_hint().0.saturating_add(1).saturating_mul(2);
+                let reserved = match self._try_reserve(reserve, false) {
+                    Err(error) if error.is_capacity_limit() => self._try_reserve(2, false),
+                    res => res,
+                };
+                if let Err(error) = reserved {
+                    if !error.is_capacity_limit() {
+                        error.panic();
+                    }
+                    // a merge is not possible, but the item may still fit
+                    if let Some(item) = next.take() {
+                        if let Err(pos) = self.as_slice().binary_search(&item) {
+                            self.insert(C::Index::from_usize(pos), item);
+                        }
+                    }
+                    next = iter.next();
+                    continue;
+                }
+            }
+            let capacity = self.buffer.capacity().to_usize();
+            let batch = (capacity - len) / 2;
+            let head = self.buffer.data_ptr_mut();
+            // SAFETY: the batch buffer is within the spare capacity, and does not
+            // overlap with the range [0, len + batch) written by the merge
+            let buf = unsafe { head.add(capacity - batch) };
+            // the length is cleared while items are moved, so that a panic in
+            // the iterator or comparison leaks the items rather than dropping
+            // them twice
+            // SAFETY: capacity of the buffer has been established as > 0
+            unsafe { self.buffer.set_length(C::Index::ZERO) };
+
+            let mut count = 0;
+            while count < batch {
+                let Some(item) = next.take() else {
+                    break;
+                };
+                if count == 0 || unsafe { &*buf.add(count - 1) } != &item {
+                    unsafe { buf.add(count).write(item) };
+                    count += 1;
+                }
+                next = iter.next();
+            }
+
+            let (mut a, mut b, mut w) = (len, count, len + count);
+            while b > 0 {
+                unsafe {
+                    let read_b = buf.add(b - 1);
+                    if a > 0 {
+                        let read_a = head.add(a - 1);
+                        match (*read_a).cmp(&*read_b) {
+                            Ordering::Greater => {
+                                ptr::copy_nonoverlapping(read_a, head.add(w - 1), 1);
+                                a -= 1;
+                                w -= 1;
+                                continue;
+                            }
+                            Ordering::Equal => {
+                                b -= 1;
+                                ptr::drop_in_place(read_b);
+                                continue;
+                            }
+                            Ordering::Less => (),
+                        }
+                    }
+                    ptr::copy_nonoverlapping(read_b, head.add(w - 1), 1);
+                    b -= 1;
+                    w -= 1;
+                }
+            }
+            // items [0, a) remain in place, followed by any gap left by duplicates
+            let merged = len + count - w;
+            if w > a && merged > 0 {
+                unsafe { ptr::copy(head.add(w), head.add(a), merged) };
+            }
+            unsafe { self.buffer.set_length(C::Index::from_usize(a + merged)) };
+        }
+    }
+
+    /// Push items from an iterator onto this vector until its existing capacity
+    /// is exhausted, returning the partially-consumed iterator.
+    ///
+    /// Unlike `extend` this method will never reallocate. Any items remaining in
+    /// the returned iterator were not added to the vector.
+    pub fn extend_within_capacity<I>(&mut self, iter: I) -> I::IntoIter
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let mut iter = iter.into_iter();
+        let mut insert = Inserter::for_buffer(&mut self.buffer);
+        while !insert.full() {
+            let Some(item) = iter.next() else { break };
+            insert.push(item);
+        }
+        let (added, new_len) = insert.complete();
+        if added > 0 {
+            // SAFETY: capacity of the buffer has been established as > 0
+            unsafe { self.buffer.set_length(C::Index::from_usize(new_len)) };
+        }
+        iter
+    }
+
+    /// Remove the items of this vector for which the predicate `pred` returns `true`,
+    /// returning an iterator over the removed items. The remaining items are compacted
+    /// in place as the iterator advances, preserving their order.
+    ///
+    /// If the iterator is dropped before it is exhausted, then any items which have
+    /// not yet been examined are retained in the vector.
+    #[inline]
+    pub fn extract_if<F>(&mut self, pred: F) -> ExtractIf<'_, C::Buffer<T>, F>
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        ExtractIf::new(&mut self.buffer, pred)
+    }
+
     unsafe fn extend_unchecked(&mut self, items: &[T])
     where
         T: Clone,
@@ -896,6 +1801,92 @@ impl<T, C: VecConfig> Vec<T, C> {
         Ok(())
     }
 
+    /// Inserts an element at position `index` if there is sufficient spare capacity,
+    /// otherwise an error is returned containing the element.
+    ///
+    /// Unlike `insert` this method will not reallocate when there’s insufficient capacity.
+    /// The caller should use `reserve` or `try_reserve` to ensure that there is enough
+    /// capacity.
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn insert_within_capacity(&mut self, index: C::Index, value: T) -> Result<(), T> {
+        let prev_len = self.buffer.length();
+        if index > prev_len {
+            index_panic();
+        }
+        if prev_len >= self.buffer.capacity() {
+            return Err(value);
+        }
+        let index = index.to_usize();
+        let tail_count = prev_len.to_usize() - index;
+        unsafe {
+            let head = self.buffer.data_ptr_mut().add(index);
+            if tail_count > 0 {
+                ptr::copy(head, head.add(1), tail_count);
+            }
+            head.write(value);
+        }
+        // SAFETY: capacity of the buffer has been established as > 0
+        unsafe { self.buffer.set_length(prev_len.saturating_add(1)) };
+        Ok(())
+    }
+
+    /// Insert the items produced by an iterator at position `index`, moving existing
+    /// elements to the right.
+    ///
+    /// When the iterator reports an exact size, the required capacity is reserved and
+    /// the existing elements are shifted only once. Otherwise, this is equivalent to
+    /// `splice(index..index, iter)`.
+    ///
+    /// Panics if `index` is out of bounds or on storage errors.
+    pub fn insert_from_iter<I>(&mut self, index: C::Index, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        let prev_len = self.buffer.length().to_usize();
+        let index_val = index.to_usize();
+        if index_val > prev_len {
+            index_panic();
+        }
+        let mut iter = iter.into_iter();
+        let ins_count = match iter.size_hint() {
+            (0, Some(0)) => return,
+            (lower, Some(upper)) if lower == upper => lower,
+            _ => {
+                self.splice(index..index, iter);
+                return;
+            }
+        };
+        match self._try_reserve(ins_count, false) {
+            Ok(_) => (),
+            Err(error) => error.panic(),
+        }
+        let tail_count = prev_len - index_val;
+        let head = unsafe { self.buffer.data_ptr_mut().add(index_val) };
+        if tail_count > 0 {
+            unsafe { ptr::copy(head, head.add(ins_count), tail_count) };
+        }
+        let mut insert =
+            Inserter::for_buffer_with_range(&mut self.buffer, index_val, ins_count, tail_count);
+        while !insert.full() {
+            let Some(item) = iter.next() else { break };
+            insert.push(item);
+        }
+        let (added, _) = insert.complete();
+        if added > 0 {
+            // SAFETY: capacity of the buffer has been established as > 0 by try_reserve
+            unsafe {
+                self.buffer
+                    .set_length(C::Index::from_usize(prev_len + added));
+            }
+        }
+        // handle an iterator which produced more items than reported
+        if let Some(item) = iter.next() {
+            let index = C::Index::from_usize(index_val + added);
+            self.splice(index..index, core::iter::once(item).chain(iter));
+        }
+    }
+
     /// Clone the elements of `other` and insert them at position `index`, moving existing
     /// elements to the right.
     ///
@@ -931,7 +1922,7 @@ impl<T, C: VecConfig> Vec<T, C> {
         let tail_count = prev_len - index;
         let head = unsafe { self.buffer.data_ptr_mut().add(index) };
         if tail_count > 0 {
-            unsafe { ptr::copy(head, head.add(index + ins_count), tail_count) };
+            unsafe { ptr::copy(head, head.add(ins_count), tail_count) };
         }
         let mut insert =
             Inserter::for_buffer_with_range(&mut self.buffer, index, ins_count, tail_count);
@@ -959,6 +1950,75 @@ impl<T, C: VecConfig> Vec<T, C> {
         }
     }
 
+    /// Moves all but the first of consecutive equal elements to the end of the vector,
+    /// returning a tuple of the retained and duplicate elements. No elements are
+    /// dropped and the length of the vector is unchanged.
+    ///
+    /// If the vector is sorted, the first returned slice contains no duplicates.
+    #[inline]
+    pub fn partition_dedup(&mut self) -> (&mut [T], &mut [T])
+    where
+        T: Eq,
+    {
+        self.partition_dedup_by(|a, b| a == b)
+    }
+
+    /// Moves all but the first of consecutive elements in the vector satisfying a
+    /// given predicate to the end of the vector, returning a tuple of the retained
+    /// and duplicate elements. No elements are dropped and the length of the vector
+    /// is unchanged.
+    ///
+    /// The `same` function is passed references to two elements from the vector, in
+    /// the same manner as [`Vec::dedup_by`]. The order of the duplicate elements is
+    /// unspecified.
+    pub fn partition_dedup_by<F>(&mut self, mut same: F) -> (&mut [T], &mut [T])
+    where
+        F: FnMut(&mut T, &mut T) -> bool,
+    {
+        let slice = self.as_mut_slice();
+        let len = slice.len();
+        if len < 2 {
+            return (slice, &mut []);
+        }
+        let mut write = 1;
+        for read in 1..len {
+            let (front, back) = slice.split_at_mut(read);
+            if !same(&mut back[0], &mut front[write - 1]) {
+                if read != write {
+                    slice.swap(read, write);
+                }
+                write += 1;
+            }
+        }
+        slice.split_at_mut(write)
+    }
+
+    /// Get the index of the maximum item in the vector, or `None` if the vector
+    /// is empty. If several items are equally maximum, the index of the last
+    /// item is returned.
+    pub fn position_max(&self) -> Option<C::Index>
+    where
+        T: Ord,
+    {
+        self.iter()
+            .enumerate()
+            .max_by(|a, b| a.1.cmp(b.1))
+            .map(|(index, _)| C::Index::from_usize(index))
+    }
+
+    /// Get the index of the minimum item in the vector, or `None` if the vector
+    /// is empty. If several items are equally minimum, the index of the first
+    /// item is returned.
+    pub fn position_min(&self) -> Option<C::Index>
+    where
+        T: Ord,
+    {
+        self.iter()
+            .enumerate()
+            .min_by(|a, b| a.1.cmp(b.1))
+            .map(|(index, _)| C::Index::from_usize(index))
+    }
+
     /// Append a new item to the end of the vector.
     ///
     /// This method will panic on any storage errors.
@@ -1102,6 +2162,40 @@ impl<T, C: VecConfig> Vec<T, C> {
         Ok(())
     }
 
+    /// Resizes the vector in-place so that the length is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than the current length, the vector is extended by the
+    /// difference, with each additional slot filled with `T::default()`. If `new_len`
+    /// is less than the current length, the vector is simply truncated.
+    ///
+    /// This method will panic on any storage errors.
+    #[inline]
+    pub fn resize_default(&mut self, new_len: C::Index)
+    where
+        T: Default,
+    {
+        match self.try_resize_with(new_len, T::default) {
+            Ok(_) => (),
+            Err(err) => err.panic(),
+        }
+    }
+
+    /// Attempts to resize the vector in-place so that the length is equal to `new_len`.
+    ///
+    /// If `new_len` is greater than the current length, the vector is extended by the
+    /// difference, with each additional slot filled with `T::default()`. If `new_len`
+    /// is less than the current length, the vector is simply truncated.
+    ///
+    /// A storage error may be returned if additional capacity is required but cannot be
+    /// provided by the associated allocator.
+    #[inline]
+    pub fn try_resize_default(&mut self, new_len: C::Index) -> Result<(), StorageError>
+    where
+        T: Default,
+    {
+        self.try_resize_with(new_len, T::default)
+    }
+
     /// Resizes the vector in-place so that the length is equal to `new_len`.
     ///
     /// If `new_len` is greater than the current length, the vector is extended by the
@@ -1142,47 +2236,224 @@ impl<T, C: VecConfig> Vec<T, C> {
     where
         F: FnMut() -> T,
     {
-        let len = self.buffer.length();
-        match new_len.cmp(&len) {
-            Ordering::Greater => {
-                let ins_count = new_len.to_usize() - len.to_usize();
-                self._try_reserve(ins_count, false)?;
-                let mut insert = Inserter::for_buffer(&mut self.buffer);
-                for _ in 0..ins_count {
-                    insert.push(f());
+        let len = self.buffer.length();
+        match new_len.cmp(&len) {
+            Ordering::Greater => {
+                let ins_count = new_len.to_usize() - len.to_usize();
+                self._try_reserve(ins_count, false)?;
+                let mut insert = Inserter::for_buffer(&mut self.buffer);
+                for _ in 0..ins_count {
+                    insert.push(f());
+                }
+                insert.complete();
+                // SAFETY: capacity of the buffer has been established as > 0 by _try_reserve
+                unsafe { self.buffer.set_length(new_len) }
+            }
+            Ordering::Less => {
+                self.truncate(new_len);
+            }
+            Ordering::Equal => {}
+        }
+        Ok(())
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// In other words, remove all elements `e` for which `f(&e)` returns `false`.
+    /// This method operates in place, visiting each element exactly once in the
+    /// original order, and preserves the order of the retained elements.
+    #[inline]
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.retain_mut(|r| f(r))
+    }
+
+    /// Retains only the elements specified by the predicate, passing a mutable reference
+    /// to the element.
+    ///
+    /// In other words, remove all elements `e` for which `f(&mut e)` returns `false`.
+    /// This method operates in place, visiting each element exactly once in the
+    /// original order, and preserves the order of the retained elements.
+    pub fn retain_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut T) -> bool,
+    {
+        self.retain_with_index(|_, item| f(item))
+    }
+
+    /// Retains only the elements specified by the predicate, passing the index of
+    /// each element along with a mutable reference to it.
+    ///
+    /// The index passed to `f` is the position of the element before any elements
+    /// were removed, so that the predicate is not affected by earlier removals. This
+    /// method operates in place, visiting each element exactly once in the original
+    /// order, and preserves the order of the retained elements.
+    pub fn retain_with_index<F>(&mut self, mut f: F)
+    where
+        F: FnMut(C::Index, &mut T) -> bool,
+    {
+        let orig_len = self.buffer.length().to_usize();
+        if orig_len == 0 {
+            return;
+        }
+        // SAFETY: capacity of the buffer has been established as > 0
+        unsafe { self.buffer.set_length(C::Index::ZERO) };
+        let mut len = 0;
+        let read_slice = DropSlice {
+            ptr: self.as_mut_ptr(),
+            len: orig_len,
+        };
+        let mut tail = self.as_mut_ptr();
+        for (index, read) in read_slice.enumerate() {
+            unsafe {
+                if f(C::Index::from_usize(index), &mut *read) {
+                    if tail != read {
+                        ptr::copy_nonoverlapping(read, tail, 1);
+                    }
+                    tail = tail.add(1);
+                    len += 1;
+                } else {
+                    ptr::drop_in_place(read);
+                }
+            }
+        }
+        unsafe { self.buffer.set_length(C::Index::from_usize(len)) };
+        if len == 0 {
+            self.buffer.vec_cleared();
+        }
+    }
+
+    /// Retains only the elements specified by a fallible predicate, passing a
+    /// mutable reference to the element.
+    ///
+    /// Elements are visited in the original order, and processing stops at the
+    /// first error returned by `f`. In that case the elements which were already
+    /// visited are retained or removed according to the predicate, while the
+    /// element producing the error and any following elements remain in the
+    /// vector. The order of the retained elements is preserved.
+    pub fn try_retain<E, F>(&mut self, mut f: F) -> Result<(), E>
+    where
+        F: FnMut(&mut T) -> Result<bool, E>,
+    {
+        struct Guard<'g, B: VecBuffer> {
+            buf: &'g mut B,
+            index: usize,
+            removed: usize,
+            orig_len: usize,
+        }
+
+        impl<'g, B: VecBuffer> Drop for Guard<'g, B> {
+            fn drop(&mut self) {
+                let tail_length = self.orig_len - self.index;
+                if tail_length > 0 && self.removed > 0 {
+                    unsafe {
+                        let read = self.buf.data_ptr_mut().add(self.index);
+                        ptr::copy(read, read.sub(self.removed), tail_length);
+                    }
+                }
+                // SAFETY: capacity of the buffer has been established as > 0
+                unsafe {
+                    self.buf
+                        .set_length(B::Index::from_usize(self.orig_len - self.removed))
+                };
+                if self.orig_len == self.removed {
+                    self.buf.vec_cleared();
                 }
-                insert.complete();
-                // SAFETY: capacity of the buffer has been established as > 0 by _try_reserve
-                unsafe { self.buffer.set_length(new_len) }
             }
-            Ordering::Less => {
-                self.truncate(new_len);
+        }
+
+        let orig_len = self.buffer.length().to_usize();
+        if orig_len == 0 {
+            return Ok(());
+        }
+        // SAFETY: capacity of the buffer has been established as > 0
+        unsafe { self.buffer.set_length(C::Index::ZERO) };
+        let mut guard = Guard {
+            buf: &mut self.buffer,
+            index: 0,
+            removed: 0,
+            orig_len,
+        };
+        while guard.index < orig_len {
+            unsafe {
+                let read = guard.buf.data_ptr_mut().add(guard.index);
+                let keep = f(&mut *read)?;
+                guard.index += 1;
+                if keep {
+                    if guard.removed > 0 {
+                        ptr::copy_nonoverlapping(read, read.sub(guard.removed), 1);
+                    }
+                } else {
+                    guard.removed += 1;
+                    ptr::drop_in_place(read);
+                }
             }
-            Ordering::Equal => {}
         }
         Ok(())
     }
 
-    /// Retains only the elements specified by the predicate.
+    /// Retains only the elements within `range` specified by the predicate, passing
+    /// a mutable reference to the element. Elements outside of `range` are always
+    /// retained.
     ///
-    /// In other words, remove all elements `e` for which `f(&e)` returns `false`.
-    /// This method operates in place, visiting each element exactly once in the
-    /// original order, and preserves the order of the retained elements.
-    #[inline]
-    pub fn retain<F>(&mut self, mut f: F)
+    /// In other words, remove all elements `e` within `range` for which `f(&mut e)`
+    /// returns `false`. This method operates in place, visiting each element within
+    /// the range exactly once in the original order, and preserves the order of the
+    /// retained elements.
+    pub fn retain_range<R, F>(&mut self, range: R, mut f: F)
     where
-        F: FnMut(&T) -> bool,
+        R: RangeBounds<C::Index>,
+        F: FnMut(&mut T) -> bool,
     {
-        self.retain_mut(|r| f(r))
+        let orig_len = self.buffer.length().to_usize();
+        let range = bounds_to_range(range, self.buffer.length());
+        if range.start > range.end || range.end > orig_len {
+            index_panic();
+        }
+        if range.start == range.end {
+            return;
+        }
+        // SAFETY: capacity of the buffer has been established as > 0
+        unsafe { self.buffer.set_length(C::Index::from_usize(range.start)) };
+        let head = self.as_mut_ptr();
+        let mut len = range.start;
+        let read_slice = DropSlice {
+            ptr: unsafe { head.add(range.start) },
+            len: range.len(),
+        };
+        let mut tail = unsafe { head.add(range.start) };
+        for read in read_slice {
+            unsafe {
+                if f(&mut *read) {
+                    if tail != read {
+                        ptr::copy_nonoverlapping(read, tail, 1);
+                    }
+                    tail = tail.add(1);
+                    len += 1;
+                } else {
+                    ptr::drop_in_place(read);
+                }
+            }
+        }
+        let tail_len = orig_len - range.end;
+        if tail_len > 0 && len != range.end {
+            unsafe { ptr::copy(head.add(range.end), tail, tail_len) };
+        }
+        unsafe { self.buffer.set_length(C::Index::from_usize(len + tail_len)) };
+        if len + tail_len == 0 {
+            self.buffer.vec_cleared();
+        }
     }
 
-    /// Retains only the elements specified by the predicate, passing a mutable reference
-    /// to the element.
+    /// Retains only the elements specified by the predicate, visiting the elements
+    /// in reverse order and passing a mutable reference to each element.
     ///
     /// In other words, remove all elements `e` for which `f(&mut e)` returns `false`.
-    /// This method operates in place, visiting each element exactly once in the
-    /// original order, and preserves the order of the retained elements.
-    pub fn retain_mut<F>(&mut self, mut f: F)
+    /// This method operates in place, visiting each element exactly once starting
+    /// from the end of the vector, and preserves the order of the retained elements.
+    pub fn retain_rev<F>(&mut self, mut f: F)
     where
         F: FnMut(&mut T) -> bool,
     {
@@ -1192,26 +2463,42 @@ impl<T, C: VecConfig> Vec<T, C> {
         }
         // SAFETY: capacity of the buffer has been established as > 0
         unsafe { self.buffer.set_length(C::Index::ZERO) };
-        let mut len = 0;
-        let read_slice = DropSlice {
-            ptr: self.as_mut_ptr(),
+        let head = self.as_mut_ptr();
+        // the unvisited items are dropped if the predicate panics
+        let mut unvisited = DropSlice {
+            ptr: head,
             len: orig_len,
         };
-        let mut tail = self.as_mut_ptr();
-        for read in read_slice {
+        // retained items are collected at the end of the buffer
+        let mut keep = orig_len;
+        while unvisited.len > 0 {
+            unvisited.len -= 1;
             unsafe {
+                let read = head.add(unvisited.len);
                 if f(&mut *read) {
-                    if tail != read {
-                        ptr::copy_nonoverlapping(read, tail, 1);
+                    keep -= 1;
+                    if keep != unvisited.len {
+                        ptr::copy_nonoverlapping(read, head.add(keep), 1);
                     }
-                    tail = tail.add(1);
-                    len += 1;
                 } else {
                     ptr::drop_in_place(read);
                 }
             }
         }
+        let len = orig_len - keep;
+        if keep > 0 && len > 0 {
+            unsafe { ptr::copy(head.add(keep), head, len) };
+        }
         unsafe { self.buffer.set_length(C::Index::from_usize(len)) };
+        if len == 0 {
+            self.buffer.vec_cleared();
+        }
+    }
+
+    /// Reverse the order of the items in the vector, in place.
+    #[inline]
+    pub fn reverse(&mut self) {
+        self.as_mut_slice().reverse();
     }
 
     /// Shrinks the capacity of the vector with a lower bound.
@@ -1234,14 +2521,12 @@ impl<T, C: VecConfig> Vec<T, C> {
     /// supplied `min_capacity`.
     ///
     /// If the current capacity is less than the lower limit, this is a no-op.
-    /// A storage error may be returned if reallocation is required but cannot be
-    /// performed by the associated allocator.
+    /// The growth strategy of the vector may choose to retain additional capacity
+    /// (see [`Grow::shrink_capacity`]). A storage error may be returned if
+    /// reallocation is required but cannot be performed by the associated allocator.
+    #[inline]
     pub fn try_shrink_to(&mut self, min_capacity: C::Index) -> Result<(), StorageError> {
-        let len = self.buffer.length().max(min_capacity);
-        if self.buffer.capacity() != len {
-            self.buffer.vec_try_resize(len, true)?;
-        }
-        Ok(())
+        vec_buffer_try_shrink::<_, C::Grow>(&mut self.buffer, min_capacity)
     }
 
     /// Shrinks the capacity of the vector as much as possible.
@@ -1264,9 +2549,16 @@ impl<T, C: VecConfig> Vec<T, C> {
     /// excess capacity, just as is the case for `with_capacity`.
     /// A storage error may be returned if reallocation is required but cannot be
     /// performed by the associated allocator.
+    ///
+    /// When the capacity of the vector already matches its length, this method
+    /// returns immediately without interacting with the allocator.
     #[inline]
     pub fn try_shrink_to_fit(&mut self) -> Result<(), StorageError> {
-        self.try_shrink_to(self.buffer.length())
+        let length = self.buffer.length();
+        if self.buffer.capacity() == length {
+            return Ok(());
+        }
+        self.try_shrink_to(length)
     }
 
     /// Access the remaining spare capacity of the vector as a mutable slice of
@@ -1277,10 +2569,54 @@ impl<T, C: VecConfig> Vec<T, C> {
     /// `set_len` method.
     #[inline]
     pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<T>] {
-        let length = self.len().into();
+        let length = self.len().to_usize();
         &mut self.buffer.as_uninit_slice()[length..]
     }
 
+    /// Access the remaining spare capacity of the vector as a slice of
+    /// `MaybeUninit<T>`.
+    #[inline]
+    pub fn spare_capacity(&self) -> &[MaybeUninit<T>] {
+        let length = self.buffer.length().to_usize();
+        let capacity = self.buffer.capacity().to_usize();
+        unsafe {
+            slice::from_raw_parts(self.buffer.data_ptr().add(length).cast(), capacity - length)
+        }
+    }
+
+    /// Access the remaining spare capacity of the vector, split such that the second
+    /// slice begins at an address which is a multiple of `align`.
+    ///
+    /// The first slice contains the padding bytes preceding the aligned region,
+    /// which always cover a whole number of items. If the requested alignment cannot
+    /// be reached at an item boundary within the spare capacity, then the entire
+    /// spare capacity is returned as padding. No padding is produced for zero-sized
+    /// types.
+    ///
+    /// Panics if `align` is not a power of two.
+    pub fn aligned_spare_capacity_mut(
+        &mut self,
+        align: usize,
+    ) -> (&mut [MaybeUninit<u8>], &mut [MaybeUninit<T>]) {
+        assert!(align.is_power_of_two(), "alignment must be a power of two");
+        let item_size = size_of::<T>();
+        let spare = self.spare_capacity_mut();
+        let pad_count = if item_size == 0 {
+            0
+        } else {
+            let offset = spare.as_ptr().cast::<u8>().align_offset(align);
+            if offset % item_size == 0 {
+                (offset / item_size).min(spare.len())
+            } else {
+                spare.len()
+            }
+        };
+        let (pad, aligned) = spare.split_at_mut(pad_count);
+        let pad =
+            unsafe { slice::from_raw_parts_mut(pad.as_mut_ptr().cast(), pad_count * item_size) };
+        (pad, aligned)
+    }
+
     /// Returns vector content as a mutable slice of `T`, along with the remaining
     /// spare capacity of the vector as a mutable slice of `MaybeUninit<T>`.
     ///
@@ -1292,7 +2628,7 @@ impl<T, C: VecConfig> Vec<T, C> {
     /// `extend_from_slice`, `extend_from_within`, `insert`, `append`, `resize` or
     /// `resize_with`, depending on your exact needs.
     pub fn split_at_spare_mut(&mut self) -> (&mut [T], &mut [MaybeUninit<T>]) {
-        let length = self.len().into();
+        let length = self.len().to_usize();
         let (data, spare) = self.buffer.as_uninit_slice().split_at_mut(length);
         (
             unsafe { slice::from_raw_parts_mut(data.as_mut_ptr().cast(), length) },
@@ -1328,6 +2664,7 @@ impl<T, C: VecConfig> Vec<T, C> {
             Ok(mut buffer) => {
                 if index_usize == 0 {
                     mem::swap(&mut buffer, &mut self.buffer);
+                    self.buffer.vec_cleared();
                 } else {
                     unsafe {
                         ptr::copy_nonoverlapping(
@@ -1346,6 +2683,38 @@ impl<T, C: VecConfig> Vec<T, C> {
         }
     }
 
+    /// Splits the collection into two at the given index, moving the elements in the
+    /// range `[at, len)` into `target`. Any existing contents of `target` are dropped,
+    /// and its capacity is reused when possible.
+    ///
+    /// Panics if `at` is out of bounds or on storage errors.
+    pub fn split_off_into(&mut self, index: C::Index, target: &mut Self) {
+        let len = self.buffer.length().to_usize();
+        let index_usize = index.to_usize();
+        if index_usize >= len {
+            index_panic();
+        }
+        let move_len = len - index_usize;
+        target.clear();
+        match target._try_reserve(move_len, false) {
+            Ok(_) => (),
+            Err(error) => error.panic(),
+        }
+        unsafe {
+            ptr::copy_nonoverlapping(
+                self.buffer.data_ptr().add(index_usize),
+                target.buffer.data_ptr_mut(),
+                move_len,
+            );
+        }
+        // SAFETY: both buffer capacities are established as > 0
+        unsafe { target.buffer.set_length(C::Index::from_usize(move_len)) };
+        unsafe { self.buffer.set_length(index) };
+        if index_usize == 0 {
+            self.buffer.vec_cleared();
+        }
+    }
+
     /// Creates a splicing iterator that replaces the specified range in the vector
     /// with the given `replace_with` iterator and yields the removed items.
     ///
@@ -1372,6 +2741,92 @@ impl<T, C: VecConfig> Vec<T, C> {
         Splice::new(&mut self.buffer, replace_with.into_iter(), range)
     }
 
+    /// Sort the items of the vector, preserving the order of equal items.
+    ///
+    /// A temporary buffer is allocated by this method, so it requires the `alloc`
+    /// feature. Use [`Vec::sort_unstable`] to sort without allocating.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn sort(&mut self)
+    where
+        T: Ord,
+    {
+        self.as_mut_slice().sort()
+    }
+
+    /// Sort the items of the vector with a comparator function, preserving the
+    /// order of equal items.
+    ///
+    /// A temporary buffer is allocated by this method, so it requires the `alloc`
+    /// feature. Use [`Vec::sort_unstable_by`] to sort without allocating.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn sort_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.as_mut_slice().sort_by(compare)
+    }
+
+    /// Sort the items of the vector with a key extraction function, preserving
+    /// the order of equal items.
+    ///
+    /// A temporary buffer is allocated by this method, so it requires the `alloc`
+    /// feature. Use [`Vec::sort_unstable_by_key`] to sort without allocating.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn sort_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.as_mut_slice().sort_by_key(f)
+    }
+
+    /// Sort the items of the vector without allocating. The order of equal
+    /// items may not be preserved.
+    #[inline]
+    pub fn sort_unstable(&mut self)
+    where
+        T: Ord,
+    {
+        self.as_mut_slice().sort_unstable()
+    }
+
+    /// Sort the items of the vector with a comparator function, without
+    /// allocating. The order of equal items may not be preserved.
+    #[inline]
+    pub fn sort_unstable_by<F>(&mut self, compare: F)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+    {
+        self.as_mut_slice().sort_unstable_by(compare)
+    }
+
+    /// Sort the items of the vector with a key extraction function, without
+    /// allocating. The order of equal items may not be preserved.
+    #[inline]
+    pub fn sort_unstable_by_key<K, F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> K,
+        K: Ord,
+    {
+        self.as_mut_slice().sort_unstable_by_key(f)
+    }
+
+    /// Swap the positions of two items in the vector.
+    ///
+    /// Panics if either `a` or `b` is out of bounds.
+    #[inline]
+    pub fn swap(&mut self, a: C::Index, b: C::Index) {
+        let len = self.buffer.length().to_usize();
+        let (a, b) = (a.to_usize(), b.to_usize());
+        if a >= len || b >= len {
+            index_panic();
+        }
+        self.as_mut_slice().swap(a, b);
+    }
+
     /// Removes an element from the vector and returns it.
     ///
     /// The removed element is replaced by the last element of the vector.
@@ -1398,7 +2853,9 @@ impl<T, C: VecConfig> Vec<T, C> {
     }
 
     /// Reduce the length of this vector to at most `length`, dropping any items
-    /// with an index `>= length`. The capacity of the vector is unchanged.
+    /// with an index `>= length`. The capacity of the vector is unchanged, unless
+    /// the vector configuration releases capacity when the vector is emptied
+    /// (see [`AutoShrink`](config::AutoShrink)).
     pub fn truncate(&mut self, length: C::Index) {
         let old_len: usize = self.len().to_usize();
         let new_len = length.to_usize().min(old_len);
@@ -1412,6 +2869,9 @@ impl<T, C: VecConfig> Vec<T, C> {
                 ptr::drop_in_place(to_drop);
             }
         }
+        if new_len == 0 {
+            self.buffer.vec_cleared();
+        }
     }
 }
 
@@ -1445,14 +2905,10 @@ impl<T, C: VecConfig> BorrowMut<[T]> for Vec<T, C> {
 
 impl<T: Clone, C: VecConfigSpawn<T>> Clone for Vec<T, C> {
     fn clone(&self) -> Self {
-        let mut inst = Self {
-            buffer: match C::vec_buffer_try_spawn(&self.buffer, self.buffer.length(), false) {
-                Ok(buf) => buf,
-                Err(err) => err.panic(),
-            },
-        };
-        inst.extend_from_slice(self);
-        inst
+        match self.try_clone() {
+            Ok(inst) => inst,
+            Err(err) => err.panic(),
+        }
     }
 
     fn clone_from(&mut self, source: &Self) {
@@ -1483,14 +2939,14 @@ impl<T, C: VecConfig> Deref for Vec<T, C> {
 
     #[inline]
     fn deref(&self) -> &Self::Target {
-        unsafe { slice::from_raw_parts(self.buffer.data_ptr(), self.len().into()) }
+        unsafe { slice::from_raw_parts(self.buffer.data_ptr(), self.len().to_usize()) }
     }
 }
 
 impl<T, C: VecConfig> DerefMut for Vec<T, C> {
     #[inline]
     fn deref_mut(&mut self) -> &mut Self::Target {
-        unsafe { slice::from_raw_parts_mut(self.buffer.data_ptr_mut(), self.len().into()) }
+        unsafe { slice::from_raw_parts_mut(self.buffer.data_ptr_mut(), self.len().to_usize()) }
     }
 }
 
@@ -1515,6 +2971,23 @@ impl<T, C: VecConfig> Extend<T> for Vec<T, C> {
             Err(error) => error.panic(),
         }
     }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn extend_one(&mut self, item: T) {
+        self.push(item);
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn extend_reserve(&mut self, additional: usize) {
+        // the reservation is only a hint, and capacity limits are reported
+        // once the additional items are added
+        match self._try_reserve(additional, false) {
+            Err(error) if !error.is_capacity_limit() => error.panic(),
+            _ => (),
+        }
+    }
 }
 
 impl<'a, T: Clone + 'a, C: VecConfig> Extend<&'a T> for Vec<T, C> {
@@ -1525,6 +2998,23 @@ impl<'a, T: Clone + 'a, C: VecConfig> Extend<&'a T> for Vec<T, C> {
             Err(error) => error.panic(),
         }
     }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn extend_one(&mut self, item: &'a T) {
+        self.push(item.clone());
+    }
+
+    #[cfg(feature = "nightly")]
+    #[inline]
+    fn extend_reserve(&mut self, additional: usize) {
+        // the reservation is only a hint, and capacity limits are reported
+        // once the additional items are added
+        match self._try_reserve(additional, false) {
+            Err(error) if !error.is_capacity_limit() => error.panic(),
+            _ => (),
+        }
+    }
 }
 
 impl<T, C: VecConfigNew<T>> FromIterator<T> for Vec<T, C> {
@@ -1574,11 +3064,12 @@ where
 #[cfg(feature = "alloc")]
 impl<T, C> From<Vec<T, C>> for alloc::boxed::Box<[T]>
 where
-    C: VecConfigAllocParts<T, Alloc = Global, Index = usize>,
+    C: VecConfig<Index = usize>,
+    C::Buffer<T>: VecBufferBoxed,
 {
     #[inline]
     fn from(vec: Vec<T, C>) -> Self {
-        alloc::vec::Vec::<T>::from(vec).into_boxed_slice()
+        vec.into_boxed_slice()
     }
 }
 
@@ -1668,6 +3159,19 @@ where
     }
 }
 
+/// Collect the remaining items of an `IntoIter` back into a `Vec`.
+///
+/// This reuses the buffer owned by the iterator, moving the remaining items
+/// to the front of the allocation rather than producing a new one.
+impl<T, C: VecConfig> From<IntoIter<C::Buffer<T>>> for Vec<T, C> {
+    #[inline]
+    fn from(iter: IntoIter<C::Buffer<T>>) -> Self {
+        Self {
+            buffer: iter.into_buffer(),
+        }
+    }
+}
+
 impl<T: Clone, C: VecConfigNew<T>> From<&[T]> for Vec<T, C> {
     #[inline]
     fn from(data: &[T]) -> Self {
@@ -1792,6 +3296,25 @@ where
 
 impl<T: Eq, C: VecConfig> Eq for Vec<T, C> {}
 
+impl<T, C1, C2> PartialOrd<Vec<T, C2>> for Vec<T, C1>
+where
+    T: PartialOrd,
+    C1: VecConfig,
+    C2: VecConfig,
+{
+    #[inline]
+    fn partial_cmp(&self, other: &Vec<T, C2>) -> Option<Ordering> {
+        self.as_slice().partial_cmp(other.as_slice())
+    }
+}
+
+impl<T: Ord, C: VecConfig> Ord for Vec<T, C> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_slice().cmp(other.as_slice())
+    }
+}
+
 impl<T1, C1, T2> PartialEq<&[T2]> for Vec<T1, C1>
 where
     T1: PartialEq<T2>,
@@ -1910,7 +3433,7 @@ where
 {
     #[inline]
     fn eq(&self, other: &alloc::vec::Vec<A>) -> bool {
-        other.eq(self)
+        self.as_slice().eq(other.as_slice())
     }
 }
 
@@ -1922,7 +3445,20 @@ where
 {
     #[inline]
     fn eq(&self, other: &allocator_api2::vec::Vec<A>) -> bool {
-        other.eq(self)
+        self.as_slice().eq(other.as_slice())
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "allocator-api2"))]
+impl<A, B, C, L> PartialEq<allocator_api2::boxed::Box<[A], L>> for Vec<B, C>
+where
+    B: PartialEq<A>,
+    C: VecConfig,
+    L: allocator_api2::alloc::Allocator,
+{
+    #[inline]
+    fn eq(&self, other: &allocator_api2::boxed::Box<[A], L>) -> bool {
+        self.as_slice().eq(&**other)
     }
 }
 
@@ -1934,7 +3470,7 @@ where
 {
     #[inline]
     fn eq(&self, other: &Vec<B, C>) -> bool {
-        other.eq(self)
+        other.as_slice().eq(self.as_slice())
     }
 }
 
@@ -1946,7 +3482,20 @@ where
 {
     #[inline]
     fn eq(&self, other: &Vec<B, C>) -> bool {
-        other.eq(self)
+        other.as_slice().eq(self.as_slice())
+    }
+}
+
+#[cfg(all(feature = "alloc", feature = "allocator-api2"))]
+impl<A, B, C, L> PartialEq<Vec<B, C>> for allocator_api2::boxed::Box<[A], L>
+where
+    A: PartialEq<B>,
+    C: VecConfig,
+    L: allocator_api2::alloc::Allocator,
+{
+    #[inline]
+    fn eq(&self, other: &Vec<B, C>) -> bool {
+        (**self).eq(other.as_slice())
     }
 }
 
@@ -2005,6 +3554,62 @@ where
     }
 }
 
+#[cfg(feature = "std")]
+impl<C: VecConfig> Vec<u8, C> {
+    /// Read bytes from `reader` into the spare capacity of this vector until the
+    /// reader is exhausted, returning the number of bytes appended.
+    ///
+    /// Additional capacity is reserved as needed. If the vector cannot be grown
+    /// (for instance, when it is backed by fixed storage) then reading stops once
+    /// the vector is full. In the case of an error, any bytes read before the
+    /// error remain in the vector.
+    pub fn fill_from_reader<R: std::io::Read>(
+        &mut self,
+        reader: &mut R,
+    ) -> std::io::Result<C::Index> {
+        const MIN_READ_RESERVE: usize = 512;
+
+        let start_len = self.len().to_usize();
+        // the number of bytes at the start of the spare capacity which have
+        // already been initialized
+        let mut init = 0;
+        loop {
+            let mut len = self.len().to_usize();
+            let capacity = self.capacity().to_usize();
+            if len == capacity {
+                match self._try_reserve(capacity.max(MIN_READ_RESERVE), false) {
+                    Ok(_) => (),
+                    Err(err) if err.is_capacity_limit() => match self._try_reserve(1, false) {
+                        Ok(_) => (),
+                        Err(err) if err.is_capacity_limit() => break,
+                        Err(err) => return Err(std::io::Error::other(err)),
+                    },
+                    Err(err) => return Err(std::io::Error::other(err)),
+                }
+                init = 0;
+            }
+            let spare = self.spare_capacity_mut();
+            spare[init..].fill(MaybeUninit::new(0));
+            init = spare.len();
+            // SAFETY: the spare capacity has been initialized
+            let buf = unsafe { &mut *(spare as *mut [MaybeUninit<u8>] as *mut [u8]) };
+            match reader.read(buf) {
+                Ok(0) => break,
+                Ok(count) => {
+                    let count = count.min(buf.len());
+                    len += count;
+                    init -= count;
+                    // SAFETY: capacity is established as > 0
+                    unsafe { self.buffer.set_length(C::Index::from_usize(len)) };
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::Interrupted => (),
+                Err(err) => return Err(err),
+            }
+        }
+        Ok(C::Index::from_usize(self.len().to_usize() - start_len))
+    }
+}
+
 #[cfg(feature = "std")]
 impl<C: VecConfig> std::io::Write for Vec<u8, C> {
     #[inline]
@@ -2018,7 +3623,7 @@ impl<C: VecConfig> std::io::Write for Vec<u8, C> {
                 unsafe { self.extend_unchecked(buf) };
                 Ok(buf.len())
             }
-            Err(StorageError::CapacityLimit) => {
+            Err(err) if err.is_capacity_limit() => {
                 // extend_within_capacity?
                 let spare = self.capacity().to_usize() - self.len().to_usize();
                 if spare > 0 {
@@ -2026,7 +3631,7 @@ impl<C: VecConfig> std::io::Write for Vec<u8, C> {
                 }
                 Ok(spare)
             }
-            Err(err) => Err(std::io::Error::new(std::io::ErrorKind::Other, err)),
+            Err(err) => Err(std::io::Error::other(err)),
         }
     }
 }
@@ -2047,6 +3652,22 @@ impl<T, C: crate::storage::RawAlloc> zeroize::ZeroizeOnDrop
 {
 }
 
+#[cfg(feature = "zeroize")]
+impl<T, C: crate::storage::RawAlloc, I: Index, G: Grow> zeroize::Zeroize
+    for Vec<T, config::Custom<crate::storage::ZeroizingAlloc<C>, I, G>>
+{
+    #[inline]
+    fn zeroize(&mut self) {
+        self.shrink_to(I::ZERO);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl<T, C: crate::storage::RawAlloc, I: Index, G: Grow> zeroize::ZeroizeOnDrop
+    for Vec<T, config::Custom<crate::storage::ZeroizingAlloc<C>, I, G>>
+{
+}
+
 // TODO
 // into_flattened
 
@@ -2061,3 +3682,12 @@ impl<T, C: crate::storage::RawAlloc> zeroize::ZeroizeOnDrop
 /// ```
 #[cfg(doctest)]
 fn _lifetime_check() {}
+
+/// ```compile_fail,E0277
+/// use flex_alloc::{storage::byte_storage, vec::Vec};
+///
+/// let mut buf = byte_storage::<100>();
+/// let v = Vec::<u8, _>::with_capacity_in_touched(10, &mut buf);
+/// ```
+#[cfg(all(doctest, feature = "std", unix))]
+fn _touched_fixed_storage_check() {}
@@ -0,0 +1,18 @@
+/// A marker trait for plain-old-data types which may be safely reinterpreted
+/// as a sequence of bytes, and constructed from an arbitrary sequence of bytes.
+///
+/// # Safety
+/// Implementing types must be `Copy`, must not contain any padding bytes,
+/// and every bit pattern of the appropriate size must be a valid value of
+/// the type.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),+) => {
+        $( unsafe impl Pod for $t {} )+
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
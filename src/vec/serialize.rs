@@ -0,0 +1,57 @@
+use core::fmt;
+use core::marker::PhantomData;
+
+use serde::de::{Deserialize, Deserializer, Error, SeqAccess, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+use crate::index::Index;
+
+use super::config::{VecConfig, VecConfigNew};
+use super::Vec;
+
+impl<T: Serialize, C: VecConfig> Serialize for Vec<T, C> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de, T: Deserialize<'de>, C: VecConfigNew<T>> Deserialize<'de> for Vec<T, C> {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(VecVisitor(PhantomData))
+    }
+}
+
+struct VecVisitor<T, C>(PhantomData<(T, C)>);
+
+impl<'de, T: Deserialize<'de>, C: VecConfigNew<T>> Visitor<'de> for VecVisitor<T, C> {
+    type Value = Vec<T, C>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a sequence")
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut vec = Vec::new();
+        // the size hint is not trusted beyond a reasonable initial allocation
+        if let Some(hint) = seq
+            .size_hint()
+            .and_then(|hint| C::Index::try_from_usize(hint.min(4096)))
+        {
+            let _ = vec.try_reserve(hint);
+        }
+        while let Some(item) = seq.next_element()? {
+            vec.try_push(item)
+                .map_err(|err| A::Error::custom(err.error()))?;
+        }
+        Ok(vec)
+    }
+}
@@ -6,6 +6,7 @@ use core::ptr;
 use super::buffer::VecBuffer;
 use super::drain::Drain;
 use super::index_panic;
+use crate::error::StorageError;
 use crate::index::{Grow, Index};
 
 /// A struct used to manage an active `splice` operation for a `Vec` instance
@@ -120,12 +121,17 @@ where
             }
 
             let mut buf_cap = self.drain.buf.capacity();
+            // at least one more item is available from the iterator
             let (min_remain, max_remain) = self.extend.size_hint();
+            let min_remain = min_remain.max(1);
             let cap_remain = buf_cap.to_usize() - self.drain.range.end - self.drain.tail_length;
             if min_remain > cap_remain {
                 let new_cap =
                     B::Index::try_from_usize(buf_cap.to_usize() + min_remain - cap_remain)
                         .expect("exceeded range of capacity");
+                if new_cap.to_usize() > G::MAX_CAPACITY {
+                    StorageError::CapacityLimit.panic();
+                }
                 let new_cap = G::next_capacity::<B::Item, _>(buf_cap, new_cap);
                 match self.drain.buf.vec_try_resize(new_cap, false) {
                     Ok(_) => (),
@@ -136,23 +142,23 @@ where
 
             // FIXME some values of size_hint could lead to more tail shifts than necessary,
             // unless we proactively move the tail further?
+            let new_tail = self
+                .drain
+                .range
+                .end
+                .saturating_add(max_remain.unwrap_or_default().max(min_remain))
+                .min(buf_cap.to_usize() - self.drain.tail_length);
+            let ins_count = new_tail - self.drain.range.end;
+            if ins_count < min_remain {
+                index_panic();
+            }
             if self.drain.tail_length > 0 {
-                let new_tail = self
-                    .drain
-                    .range
-                    .end
-                    .saturating_add(max_remain.unwrap_or_default().max(min_remain))
-                    .min(buf_cap.to_usize());
-                let ins_count = new_tail - self.drain.range.end;
-                if ins_count < min_remain {
-                    index_panic();
-                }
                 unsafe {
                     let head = self.drain.buf.data_ptr_mut().add(self.drain.range.end);
                     ptr::copy(head, head.add(ins_count), self.drain.tail_length);
                 }
-                self.drain.range.end += ins_count;
             }
+            self.drain.range.end += ins_count;
         }
     }
 }
@@ -0,0 +1,143 @@
+use core::fmt;
+
+use crate::error::StorageError;
+use crate::index::Index;
+
+use super::config::VecConfig;
+use super::{index_panic, Vec};
+
+/// A positioned writer over a byte `Vec`, supporting seeking backward to
+/// overwrite previously written data.
+///
+/// Writing at the current position overwrites any existing bytes, and extends
+/// the vector once the end of its current contents is reached.
+pub struct VecWriter<'a, C: VecConfig> {
+    vec: &'a mut Vec<u8, C>,
+    position: usize,
+}
+
+impl<'a, C: VecConfig> VecWriter<'a, C> {
+    /// Create a new writer, positioned at the end of the vector.
+    #[inline]
+    pub fn new(vec: &'a mut Vec<u8, C>) -> Self {
+        let position = vec.len().to_usize();
+        Self { vec, position }
+    }
+
+    /// Get the current write position.
+    #[inline]
+    pub fn position(&self) -> C::Index {
+        C::Index::from_usize(self.position)
+    }
+
+    /// Move the write position. This method will panic if the position is
+    /// greater than the length of the vector.
+    #[inline]
+    pub fn seek(&mut self, position: C::Index) {
+        let position = position.to_usize();
+        if position > self.vec.len().to_usize() {
+            index_panic();
+        }
+        self.position = position;
+    }
+
+    /// Access the contained vector.
+    #[inline]
+    pub fn get_ref(&self) -> &Vec<u8, C> {
+        self.vec
+    }
+
+    /// Release the reference to the contained vector.
+    #[inline]
+    pub fn into_inner(self) -> &'a mut Vec<u8, C> {
+        self.vec
+    }
+
+    /// Write a slice of bytes at the current position, advancing the position.
+    ///
+    /// This method will panic on any storage errors.
+    #[inline]
+    pub fn write_slice(&mut self, data: &[u8]) {
+        match self.try_write_slice(data) {
+            Ok(_) => (),
+            Err(error) => error.panic(),
+        }
+    }
+
+    /// Try to write a slice of bytes at the current position, advancing the position.
+    /// In the case of an error, no changes are made to the vector.
+    pub fn try_write_slice(&mut self, data: &[u8]) -> Result<(), StorageError> {
+        self.try_overwrite_at(C::Index::from_usize(self.position), data)?;
+        self.position += data.len();
+        Ok(())
+    }
+
+    /// Write a slice of bytes at an earlier position, without changing the
+    /// current write position. The vector is extended if the written data
+    /// passes the end of its current contents. This method will panic if
+    /// the position is greater than the length of the vector.
+    ///
+    /// This method will panic on any storage errors.
+    #[inline]
+    pub fn overwrite_at(&mut self, position: C::Index, data: &[u8]) {
+        match self.try_overwrite_at(position, data) {
+            Ok(_) => (),
+            Err(error) => error.panic(),
+        }
+    }
+
+    /// Try to write a slice of bytes at an earlier position, without changing
+    /// the current write position. The vector is extended if the written data
+    /// passes the end of its current contents. This method will panic if
+    /// the position is greater than the length of the vector.
+    ///
+    /// In the case of an error, no changes are made to the vector.
+    pub fn try_overwrite_at(
+        &mut self,
+        position: C::Index,
+        data: &[u8],
+    ) -> Result<(), StorageError> {
+        let position = position.to_usize();
+        let len = self.vec.len().to_usize();
+        if position > len {
+            index_panic();
+        }
+        let overlap = (len - position).min(data.len());
+        let (head, tail) = data.split_at(overlap);
+        if !tail.is_empty() {
+            self.vec.try_extend_from_slice(tail)?;
+        }
+        self.vec[position..position + overlap].copy_from_slice(head);
+        Ok(())
+    }
+}
+
+impl<'a, C: VecConfig> fmt::Debug for VecWriter<'a, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VecWriter")
+            .field("vec", &self.vec)
+            .field("position", &self.position)
+            .finish()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<'a, C: VecConfig> std::io::Write for VecWriter<'a, C> {
+    #[inline]
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let len = self.vec.len().to_usize();
+        let overlap = (len - self.position).min(buf.len());
+        let (head, tail) = buf.split_at(overlap);
+        let mut count = overlap;
+        if !tail.is_empty() {
+            count += std::io::Write::write(&mut *self.vec, tail)?;
+        }
+        self.vec[self.position..self.position + overlap].copy_from_slice(head);
+        self.position += count;
+        Ok(count)
+    }
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "serde")]
+
+use flex_alloc::vec::{InlineVec, Vec as FlexVec};
+use serde_test::{assert_de_tokens_error, assert_tokens, Token};
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_serde_tokens() {
+    let v = FlexVec::<u8>::from_slice(&[1, 2, 3]);
+    assert_tokens(
+        &v,
+        &[
+            Token::Seq { len: Some(3) },
+            Token::U8(1),
+            Token::U8(2),
+            Token::U8(3),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn inline_vec_serde_tokens() {
+    let v = InlineVec::<u16, 4>::from_slice(&[10, 20]);
+    assert_tokens(
+        &v,
+        &[
+            Token::Seq { len: Some(2) },
+            Token::U16(10),
+            Token::U16(20),
+            Token::SeqEnd,
+        ],
+    );
+}
+
+#[test]
+fn inline_vec_deserialize_overflow() {
+    // deserialization stops at the first item exceeding the capacity
+    let mut tokens = std::vec![Token::Seq { len: Some(20) }];
+    tokens.extend((0..17).map(Token::U8));
+    assert_de_tokens_error::<InlineVec<u8, 16>>(&tokens, "Exceeded storage capacity limit");
+}
@@ -1,30 +1,82 @@
+#![cfg_attr(feature = "nightly", feature(extend_one))]
+
 use core::marker::PhantomData as Cfg;
-use core::mem::ManuallyDrop;
 #[cfg(feature = "alloc")]
-use core::mem::{size_of, size_of_val};
+use core::mem::size_of_val;
+use core::mem::{size_of, ManuallyDrop};
 
 use rstest::rstest;
 
+#[cfg(feature = "alloc")]
+use core::{alloc::Layout, cell::Cell, ptr::NonNull};
+
 #[cfg(feature = "alloc")]
 use const_default::ConstDefault;
 use flex_alloc::{
     index::Index,
-    storage::{aligned_byte_storage, array_storage, byte_storage, Inline},
+    storage::{
+        aligned_byte_storage, array_storage, byte_storage, checked_aligned_byte_storage, Inline,
+    },
     vec::{
         config::{VecConfig, VecConfigNew, VecNewIn},
-        InlineVec, Vec as FlexVec,
+        Entry, InlineSmallVec, InlineVec, Vec as FlexVec, VecWriter,
     },
 };
 
 #[cfg(feature = "alloc")]
 use flex_alloc::{
-    storage::{Global, Thin, WithAlloc},
+    storage::{Bump, CountingAlloc, Global, RawAlloc, Thin, WithAlloc},
     vec,
-    vec::{config::Custom, ThinVec},
+    vec::{
+        config::{AutoShrink, Custom},
+        ThinVec,
+    },
+    StorageError,
 };
 
 const SLICE: &[usize] = &[1, 2, 3, 4, 5];
 
+/// An allocator wrapper which records the number of calls made to it.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Default)]
+struct TestAlloc {
+    calls: Cell<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl TestAlloc {
+    fn calls(&self) -> usize {
+        self.calls.get()
+    }
+
+    fn record(&self) {
+        self.calls.set(self.calls.get() + 1);
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl RawAlloc for &TestAlloc {
+    fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+        self.record();
+        Global.try_alloc(layout)
+    }
+
+    unsafe fn try_resize(
+        &self,
+        ptr: NonNull<u8>,
+        old_layout: Layout,
+        new_layout: Layout,
+    ) -> Result<NonNull<[u8]>, StorageError> {
+        self.record();
+        Global.try_resize(ptr, old_layout, new_layout)
+    }
+
+    unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
+        self.record();
+        Global.release(ptr, layout)
+    }
+}
+
 #[derive(Default, Copy, Clone)]
 struct Zst;
 
@@ -55,6 +107,7 @@ fn vec_new_as_slice<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
 #[cfg_attr(feature="alloc", case::custom(Custom::<Global, u8>::default()))]
 #[case::array(&mut array_storage::<_, 10>())]
 #[case::aligned(&mut aligned_byte_storage::<usize, 1000>())]
+#[case::checked(&mut checked_aligned_byte_storage::<usize, 8>())]
 #[case::bytes(&mut byte_storage::<1000>())]
 #[case::inline(Inline::<10>)]
 fn vec_new_in_as_slice<C: VecNewIn<usize>>(#[case] buf: C) {
@@ -113,6 +166,83 @@ fn vec_clone<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
     assert_eq!(v2, [1]);
 }
 
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_try_clone<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let v = FlexVec::<usize, C>::from_slice(SLICE);
+    let v2 = v.try_clone().expect("error cloning");
+    assert_eq!(v, v2);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_with_capacity_usable_size() {
+    /// An allocator which rounds each allocation up to a multiple of 64 bytes.
+    #[derive(Default)]
+    struct RoundingAlloc(Cell<usize>);
+
+    impl RoundingAlloc {
+        fn rounded(layout: Layout) -> Layout {
+            Layout::from_size_align((layout.size() + 63) & !63, layout.align()).unwrap()
+        }
+    }
+
+    impl RawAlloc for &RoundingAlloc {
+        fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+            self.0.set(self.0.get() + 1);
+            Global.try_alloc(RoundingAlloc::rounded(layout))
+        }
+
+        unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
+            Global.release(ptr, RoundingAlloc::rounded(layout))
+        }
+    }
+
+    let alloc = RoundingAlloc::default();
+    let mut v = FlexVec::<u64, _>::with_capacity_in(3, &alloc);
+    assert_eq!(v.capacity(), 8);
+    v.extend(0..8);
+    assert_eq!(alloc.0.get(), 1);
+    v.push(8);
+    assert_eq!(alloc.0.get(), 2);
+    assert_eq!(v.capacity(), 16);
+
+    #[cfg(all(feature = "std", unix))]
+    {
+        let v = FlexVec::<u64, _>::with_capacity_in_touched(3, &alloc);
+        assert_eq!(v.capacity(), 8);
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_try_clone_error() {
+    /// An allocator which fails once a limited number of allocations are made.
+    struct LimitAlloc(Cell<usize>);
+
+    impl RawAlloc for &LimitAlloc {
+        fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+            let remain = self.0.get();
+            if remain == 0 {
+                return Err(StorageError::AllocError);
+            }
+            self.0.set(remain - 1);
+            Global.try_alloc(layout)
+        }
+
+        unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
+            Global.release(ptr, layout)
+        }
+    }
+
+    let alloc = LimitAlloc(Cell::new(1));
+    let v = FlexVec::<usize, _>::from_slice_in(SLICE, &alloc);
+    assert_eq!(v.try_clone().unwrap_err(), StorageError::AllocError);
+}
+
 #[rstest]
 #[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
 #[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
@@ -246,6 +376,20 @@ fn vec_dedup<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
     assert_eq!(vec, &[0, 1, 0, 2, 4, 7]);
 }
 
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_dedup_count<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut vec = FlexVec::<usize, C>::from_iter([0, 1, 1, 0, 2, 4, 7, 7, 7]);
+    assert_eq!(vec.dedup_count().to_usize(), 3);
+    assert_eq!(vec, &[0, 1, 0, 2, 4, 7]);
+    assert_eq!(vec.dedup_count().to_usize(), 0);
+    assert_eq!(vec.dedup_by_count(|a, b| *a % 2 == *b % 2).to_usize(), 2);
+    assert_eq!(vec, &[0, 1, 0, 7]);
+}
+
 #[rstest]
 #[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
 #[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
@@ -285,6 +429,69 @@ fn vec_drain_forget<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
     assert_eq!(&b[..], &[0, 1, 2, 3, 4]);
 }
 
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_split_truncate<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut b = FlexVec::<usize, C>::from_iter(0..10);
+    let cap = b.capacity();
+    assert!(b.split_truncate(C::Index::from_usize(6)).eq([6, 7, 8, 9]));
+    assert_eq!(&b[..], &[0, 1, 2, 3, 4, 5]);
+    assert_eq!(b.capacity(), cap);
+    assert_eq!(b.split_truncate(C::Index::from_usize(8)).len(), 0);
+    assert_eq!(b.len().to_usize(), 6);
+    assert!(b.split_truncate(C::Index::ZERO).eq(0..6));
+    assert!(b.is_empty());
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_drain_keep_rest<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut b = FlexVec::<usize, C>::from_iter(0..10);
+    let mut drain = b.drain(C::Index::from_usize(2)..C::Index::from_usize(8));
+    assert_eq!(drain.next(), Some(2));
+    assert_eq!(drain.next(), Some(3));
+    assert_eq!(drain.next_back(), Some(7));
+    drain.keep_rest();
+    assert_eq!(&b[..], &[0, 1, 4, 5, 6, 8, 9]);
+
+    let mut drain = b.drain(C::Index::from_usize(1)..C::Index::from_usize(4));
+    drain.next_back();
+    drain.keep_rest();
+    assert_eq!(&b[..], &[0, 1, 4, 6, 8, 9]);
+
+    b.drain(C::Index::from_usize(1)..C::Index::from_usize(4))
+        .keep_rest();
+    assert_eq!(&b[..], &[0, 1, 4, 6, 8, 9]);
+}
+
+#[test]
+fn vec_drain_drops_remaining() {
+    use std::rc::Rc;
+
+    let item = Rc::new(());
+    let mut v = InlineVec::<Rc<()>, 10>::from_iter((0..6).map(|_| item.clone()));
+    let mut drain = v.drain(1..5);
+    drain.next();
+    drop(drain);
+    assert_eq!(v.len(), 2);
+    assert_eq!(Rc::strong_count(&item), 3);
+    drop(v);
+
+    let mut v = InlineVec::<Rc<()>, 10>::from_iter((0..6).map(|_| item.clone()));
+    let mut drain = v.drain(1..5);
+    drain.next();
+    drain.keep_rest();
+    assert_eq!(v.len(), 5);
+    drop(v);
+    assert_eq!(Rc::strong_count(&item), 1);
+}
+
 #[rstest]
 #[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
 #[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
@@ -312,6 +519,41 @@ fn vec_into_iter_skip<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
     assert_eq!(iter.next(), None);
 }
 
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_into_iter_clone<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>)
+where
+    C::Buffer<usize>: flex_alloc::vec::buffer::VecBufferSpawn,
+{
+    let mut iter = FlexVec::<usize, C>::from_iter(0..4).into_iter();
+    assert_eq!(iter.next(), Some(0));
+    iter.as_mut_slice()[0] = 10;
+    let iter2 = iter.clone();
+    assert_eq!(iter2.as_slice(), &[10, 2, 3]);
+    assert!(iter.eq(iter2));
+
+    let mut iter = FlexVec::<usize, C>::from_iter(0..1).into_iter();
+    iter.next();
+    assert_eq!(iter.clone().next(), None);
+}
+
+#[test]
+fn vec_into_iter_drops_remaining() {
+    use std::rc::Rc;
+
+    let item = Rc::new(());
+    let mut iter = InlineVec::<Rc<()>, 10>::from_iter((0..4).map(|_| item.clone())).into_iter();
+    iter.next();
+    let iter2 = iter.clone();
+    assert_eq!(Rc::strong_count(&item), 7);
+    drop(iter);
+    drop(iter2);
+    assert_eq!(Rc::strong_count(&item), 1);
+}
+
 #[rstest]
 #[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
 #[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
@@ -332,6 +574,118 @@ fn vec_retain() {
     assert_eq!(b, &[2, 4]);
 }
 
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_retain_with_index<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from_iter([5, 5, 6, 6, 7, 7]);
+    let mut seen = std::vec::Vec::new();
+    v.retain_with_index(|idx, item| {
+        seen.push(idx.to_usize());
+        *item += 1;
+        idx.to_usize() % 2 == 0
+    });
+    assert_eq!(v, &[6, 7, 8]);
+    assert_eq!(seen, [0, 1, 2, 3, 4, 5]);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_try_retain<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from_iter([1, 2, 3, 4, 5, 6]);
+    assert_eq!(v.try_retain(|item| Ok::<_, ()>(*item % 2 == 0)), Ok(()));
+    assert_eq!(v, &[2, 4, 6]);
+
+    let mut v = FlexVec::<usize, C>::from_iter([1, 2, 3, 4, 5, 6]);
+    let res = v.try_retain(|item| {
+        if *item == 4 {
+            Err("invalid")
+        } else {
+            *item *= 10;
+            Ok(*item != 20)
+        }
+    });
+    assert_eq!(res, Err("invalid"));
+    assert_eq!(v, &[10, 30, 4, 5, 6]);
+}
+
+#[test]
+fn vec_try_retain_drop() {
+    use std::rc::Rc;
+
+    let item = Rc::new(());
+    let mut v = InlineVec::<Rc<()>, 10>::from_iter((0..6).map(|_| item.clone()));
+    let mut count = 0;
+    let res = v.try_retain(|_| {
+        count += 1;
+        match count {
+            4 => Err(()),
+            _ => Ok(count % 2 == 0),
+        }
+    });
+    assert_eq!(res, Err(()));
+    assert_eq!(v.len(), 4);
+    assert_eq!(Rc::strong_count(&item), 5);
+    drop(v);
+    assert_eq!(Rc::strong_count(&item), 1);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_retain_range<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from([1, 2, 3, 4, 5, 6, 7, 8]);
+    let mut visited = 0;
+    v.retain_range(C::Index::from_usize(2)..C::Index::from_usize(6), |i| {
+        visited += 1;
+        *i % 2 == 0
+    });
+    assert_eq!(visited, 4);
+    assert_eq!(v, &[1, 2, 4, 6, 7, 8]);
+
+    v.retain_range(C::Index::from_usize(4).., |_| false);
+    assert_eq!(v, &[1, 2, 4, 6]);
+
+    v.retain_range(..C::Index::from_usize(2), |_| false);
+    assert_eq!(v, &[4, 6]);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_retain_rev<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from([1, 2, 3, 4, 5, 6, 7, 8, 9]);
+    let mut seen = 0;
+    // keep the last two even numbers
+    v.retain_rev(|i| {
+        if *i % 2 == 0 && seen < 2 {
+            seen += 1;
+            true
+        } else {
+            false
+        }
+    });
+    assert_eq!(v, &[6, 8]);
+
+    let mut v = FlexVec::<usize, C>::from([1, 2, 3]);
+    let mut order = std::vec::Vec::new();
+    v.retain_rev(|i| {
+        order.push(*i);
+        true
+    });
+    assert_eq!(order, [3, 2, 1]);
+    assert_eq!(v, &[1, 2, 3]);
+}
+
 #[rstest]
 #[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
 #[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
@@ -343,6 +697,19 @@ fn vec_resize<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
     assert_eq!(v, &[1, 2, 3, 10, 10]);
 }
 
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_resize_default<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from([1, 2, 3]);
+    v.resize_default(C::Index::from_usize(5));
+    assert_eq!(v, &[1, 2, 3, 0, 0]);
+    v.try_resize_default(C::Index::from_usize(2)).unwrap();
+    assert_eq!(v, &[1, 2]);
+}
+
 #[rstest]
 #[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
 #[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
@@ -366,6 +733,33 @@ fn vec_split_off<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
     assert_eq!(v2, &[4, 5, 6]);
 }
 
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_split_off_into<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v1 = FlexVec::<usize, C>::from([1, 2, 3, 4, 5, 6]);
+    let mut v2 = FlexVec::<usize, C>::with_capacity(C::Index::from_usize(10));
+    v2.extend([7, 8]);
+    let cap = v2.capacity();
+    v1.split_off_into(C::Index::from_usize(3), &mut v2);
+    assert_eq!(v1, &[1, 2, 3]);
+    assert_eq!(v2, &[4, 5, 6]);
+    assert_eq!(v2.capacity(), cap);
+    v1.split_off_into(C::Index::ZERO, &mut v2);
+    assert!(v1.is_empty());
+    assert_eq!(v2, &[1, 2, 3]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid element index")]
+fn vec_split_off_into_out_of_bounds() {
+    let mut v1 = InlineVec::<usize, 10>::from_slice(&[1, 2]);
+    let mut v2 = InlineVec::<usize, 10>::new();
+    v1.split_off_into(3, &mut v2);
+}
+
 #[rstest]
 #[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
 #[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
@@ -383,6 +777,21 @@ fn vec_splice<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
     assert_eq!(&v[..], &[0, 11, 12, 13, 14, 15, 5, 6, 7, 8, 9])
 }
 
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<20>>)]
+fn vec_splice_unsized<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from_slice(&[1, 2, 3, 4, 5]);
+    let one = C::Index::from_usize(1);
+    v.splice(one..one, (20..30).filter(|i| i % 4 == 0));
+    assert_eq!(v, &[1, 20, 24, 28, 2, 3, 4, 5]);
+    let end = v.len();
+    v.splice(end..end, (30..40).filter(|i| i % 4 == 0));
+    assert_eq!(v, &[1, 20, 24, 28, 2, 3, 4, 5, 32, 36]);
+}
+
 #[rstest]
 #[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
 #[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
@@ -505,6 +914,50 @@ fn vec_into_allocator_api2_boxed_slice() {
     assert_eq!(vec.capacity(), SLICE.len());
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_eq_std_vec() {
+    let vec = FlexVec::<usize, Inline<4>>::from_slice(&[1, 2, 3]);
+    let std_vec = std::vec![1usize, 2, 3];
+    assert!(vec == std_vec);
+    assert!(std_vec == vec);
+    assert!(vec != std::vec![1usize, 2]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_eq_std_vec_asymmetric() {
+    #[derive(Debug)]
+    struct Key(usize);
+
+    impl PartialEq<Key> for usize {
+        fn eq(&self, other: &Key) -> bool {
+            *self == other.0
+        }
+    }
+
+    let vec = FlexVec::<usize>::from_slice(&[1, 2]);
+    let std_vec = std::vec![Key(1), Key(2)];
+    assert!(vec == std_vec);
+    assert!(std_vec == vec);
+}
+
+#[cfg(all(feature = "alloc", feature = "allocator-api2"))]
+#[test]
+fn vec_eq_allocator_api2() {
+    let vec = FlexVec::<usize, Inline<4>>::from_slice(&[1, 2, 3]);
+    let api_vec = allocator_api2::vec![1usize, 2, 3];
+    assert!(vec == api_vec);
+    assert!(api_vec == vec);
+    let boxed = api_vec.into_boxed_slice();
+    assert!(vec == boxed);
+    assert!(boxed == vec);
+    let other: allocator_api2::boxed::Box<[usize]> =
+        allocator_api2::vec![1usize].into_boxed_slice();
+    assert!(vec != other);
+    assert!(other != vec);
+}
+
 #[cfg(feature = "alloc")]
 #[test]
 fn vec_into_std_boxed_slice() {
@@ -561,6 +1014,91 @@ fn vec_new_in_array_with_alloc() {
     b.extend_from_slice(&[0, 1, 2, 3, 4, 5, 6, 7]);
 }
 
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_new_in_slice_with_alloc() {
+    let mut z = array_storage::<usize, 8>();
+    let (head, tail) = z.as_uninit_slice().split_at_mut(4);
+    let mut a = FlexVec::new_in(head.with_alloc());
+    a.extend_from_slice(&[1, 2, 3, 4]);
+    assert!(!a.spilled());
+    let mut b = FlexVec::new_in(tail.with_alloc_in(Global));
+    b.extend_from_slice(&[5, 6]);
+    assert!(!b.spilled());
+    a.push(5);
+    assert!(a.spilled());
+    assert_eq!(a, &[1, 2, 3, 4, 5]);
+    assert_eq!(b, &[5, 6]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_spill_storage_scoped_thread() {
+    let mut z = array_storage::<usize, 4>();
+    let mut v = FlexVec::from_slice_in(&[1, 2, 3], z.with_alloc());
+    let mut buf = byte_storage::<64>();
+    let shared = FlexVec::from_slice_in(&[4, 5], buf.with_alloc());
+    std::thread::scope(|s| {
+        s.spawn(|| {
+            v.extend_from_slice(&shared);
+            v.push(6);
+        });
+        s.spawn(|| assert_eq!(shared.len(), 2));
+    });
+    assert!(v.spilled());
+    assert_eq!(v, &[1, 2, 3, 4, 5, 6]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_new_in_chain_storage() {
+    use flex_alloc::storage::ChainAlloc;
+
+    let alloc = CountingAlloc::new(Global);
+    let mut first = array_storage::<usize, 2>();
+    let mut second = aligned_byte_storage::<usize, { 6 * size_of::<usize>() }>();
+    let mut v = FlexVec::new_in(first.with_alloc_in(ChainAlloc::new(&mut second, &alloc)));
+    v.extend([1, 2]);
+    assert!(!v.spilled());
+    v.push(3);
+    assert!(v.spilled());
+    assert_eq!(v.capacity(), 6);
+    let ptr = v.as_ptr().cast();
+    assert!(v.allocator().inner().is_fixed_storage(ptr));
+    v.extend([4, 5, 6]);
+    assert_eq!(alloc.live_allocations(), 0);
+    v.push(7);
+    assert_eq!(alloc.live_allocations(), 1);
+    let ptr = v.as_ptr().cast();
+    assert!(!v.allocator().inner().is_fixed_storage(ptr));
+    assert_eq!(v, &[1, 2, 3, 4, 5, 6, 7]);
+    drop(v);
+    assert_eq!(alloc.live_allocations(), 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_chain_alloc_retains_fixed_after_oversize() {
+    use flex_alloc::storage::ChainAlloc;
+
+    let alloc = CountingAlloc::new(Global);
+    let mut buf = aligned_byte_storage::<usize, { 4 * size_of::<usize>() }>();
+    let chain = ChainAlloc::new(&mut buf, &alloc);
+    let large = Layout::array::<usize>(10).unwrap();
+    let big = chain.try_alloc(large).expect("error allocating");
+    assert!(!chain.is_fixed_storage(big.as_ptr().cast()));
+    assert_eq!(alloc.live_allocations(), 1);
+    let small = Layout::array::<usize>(2).unwrap();
+    let fixed = chain.try_alloc(small).expect("error allocating");
+    assert!(chain.is_fixed_storage(fixed.as_ptr().cast()));
+    assert_eq!(alloc.live_allocations(), 1);
+    unsafe {
+        chain.release(fixed.cast(), small);
+        chain.release(big.cast(), large);
+    }
+    assert_eq!(alloc.live_allocations(), 0);
+}
+
 #[test]
 fn vec_new_in_array_zst() {
     struct Item;
@@ -678,3 +1216,1595 @@ fn vec_macro() {
     let v = vec![in Global; 1, 2, 3];
     assert_eq!(&v, &[1, 2, 3]);
 }
+
+#[cfg(feature = "nightly")]
+#[test]
+fn vec_extend_one() {
+    let mut v = InlineVec::<usize, 10>::new();
+    Extend::<usize>::extend_reserve(&mut v, 3);
+    // a hint exceeding the capacity is ignored
+    Extend::<&usize>::extend_reserve(&mut v, 20);
+    v.extend_one(1);
+    v.extend_one(&2);
+    v.extend_one(3);
+    assert_eq!(v, &[1, 2, 3]);
+}
+
+#[test]
+fn vec_accumulate() {
+    let mut v = InlineVec::<usize, 10>::from_iter([1, 2, 3, 4]);
+    v.accumulate(|prev, cur| prev + cur);
+    assert_eq!(v, &[1, 3, 6, 10]);
+
+    let mut v = InlineVec::<usize, 10>::new();
+    v.accumulate(|prev, cur| prev + cur);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn vec_entry() {
+    let mut v = InlineVec::<usize, 10>::from_iter([1, 3, 5]);
+    let Entry::Occupied(item) = v.entry(&3) else {
+        panic!("expected occupied entry");
+    };
+    assert_eq!(*item, 3);
+    *v.entry(&3).or_insert(0) += 1;
+    assert_eq!(v, &[1, 4, 5]);
+
+    let Entry::Vacant(entry) = v.entry(&2) else {
+        panic!("expected vacant entry");
+    };
+    assert_eq!(entry.index(), 1);
+    assert_eq!(*v.entry(&2).or_insert(2), 2);
+    assert_eq!(*v.entry(&6).or_insert_with(|| 6), 6);
+    assert_eq!(v, &[1, 2, 4, 5, 6]);
+}
+
+#[test]
+fn vec_entry_by_key() {
+    let mut v = InlineVec::<(&str, usize), 10>::new();
+    for word in ["b", "a", "b", "c", "b"] {
+        v.entry_by_key(&word, |item| &item.0).or_insert((word, 0)).1 += 1;
+    }
+    assert_eq!(v, &[("a", 1), ("b", 3), ("c", 1)]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_entry_by_borrowed_key() {
+    let mut v = FlexVec::<String>::from_iter(["a", "c"].map(String::from));
+    assert!(matches!(
+        v.entry_by_key("c", |s| s.as_str()),
+        Entry::Occupied(_)
+    ));
+    v.entry_by_key("b", |s| s.as_str())
+        .or_insert_with(|| "b".to_string());
+    assert_eq!(v, &["a", "b", "c"]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_shrink_to_fit_exact_noop() {
+    let alloc = TestAlloc::default();
+    let mut v = FlexVec::<usize, _>::new_in(&alloc);
+    let calls = alloc.calls();
+    v.shrink_to_fit();
+    assert_eq!(alloc.calls(), calls);
+
+    v.reserve_exact(4);
+    v.extend([1, 2, 3, 4]);
+    assert_eq!(v.capacity(), 4);
+    let calls = alloc.calls();
+    v.shrink_to_fit();
+    assert_eq!(alloc.calls(), calls);
+    assert_eq!(v, &[1, 2, 3, 4]);
+}
+
+#[test]
+fn vec_dedup_window() {
+    let mut v = InlineVec::<usize, 10>::from_iter([1, 2, 1, 3, 1]);
+    v.dedup_window(2, |a, b| a == b);
+    assert_eq!(v, &[1, 2, 3, 1]);
+
+    let mut v = InlineVec::<usize, 10>::from_iter([1, 2, 1, 3, 1]);
+    v.dedup_window(3, |a, b| a == b);
+    assert_eq!(v, &[1, 2, 3]);
+
+    let mut v = InlineVec::<usize, 10>::from_iter([1, 1, 2, 1]);
+    v.dedup_window(0, |a, b| a == b);
+    assert_eq!(v, &[1, 1, 2, 1]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_from_into_iter_reuse() {
+    let alloc = TestAlloc::default();
+    let mut v = FlexVec::<usize, _>::new_in(&alloc);
+    v.extend(0..10);
+    let capacity = v.capacity();
+    let calls = alloc.calls();
+    let mut iter = v.into_iter();
+    assert_eq!(iter.next(), Some(0));
+    assert_eq!(iter.next_back(), Some(9));
+    let v = FlexVec::<usize, &TestAlloc>::from(iter);
+    assert_eq!(alloc.calls(), calls);
+    assert_eq!(v.capacity(), capacity);
+    assert_eq!(v, &[1, 2, 3, 4, 5, 6, 7, 8]);
+}
+
+#[test]
+fn vec_exact_chunks() {
+    let v = InlineVec::<usize, 10>::from_iter([1, 2, 3, 4, 5, 6]);
+    assert_eq!(v.exact_chunks::<2>(), Some(&[[1, 2], [3, 4], [5, 6]][..]));
+    assert_eq!(v.exact_chunks::<3>(), Some(&[[1, 2, 3], [4, 5, 6]][..]));
+    assert_eq!(v.exact_chunks::<4>(), None);
+
+    let v = InlineVec::<usize, 10>::new();
+    assert_eq!(v.exact_chunks::<4>(), Some(&[][..]));
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<3>>)]
+fn vec_insert_within_capacity<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::with_capacity(C::Index::from_usize(3));
+    let cap = v.capacity().to_usize();
+    v.extend(1..=cap);
+    assert_eq!(v.insert_within_capacity(C::Index::ZERO, 0), Err(0));
+    v.pop();
+    assert_eq!(v.insert_within_capacity(C::Index::ZERO, 0), Ok(()));
+    assert_eq!(v.len().to_usize(), cap);
+    assert_eq!(v[0], 0);
+    assert_eq!(v[1], 1);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_collect_result_option<C: VecConfigNew<i32>>(#[case] _config: Cfg<C>) {
+    let res: Result<FlexVec<i32, C>, &str> = [1, 2, 3].into_iter().map(Ok).collect();
+    assert_eq!(res.unwrap(), &[1, 2, 3]);
+
+    let mut seen = 0;
+    let res: Result<FlexVec<i32, C>, &str> = [1, 2, 3]
+        .into_iter()
+        .map(|i| {
+            seen += 1;
+            if i == 2 {
+                Err("invalid")
+            } else {
+                Ok(i)
+            }
+        })
+        .collect();
+    assert_eq!(res, Err("invalid"));
+    assert_eq!(seen, 2);
+
+    let res: Option<FlexVec<i32, C>> = [Some(1), Some(2)].into_iter().collect();
+    assert_eq!(res.unwrap(), &[1, 2]);
+    let res: Option<FlexVec<i32, C>> = [Some(1), None].into_iter().collect();
+    assert!(res.is_none());
+}
+
+#[test]
+fn vec_aligned_spare_capacity_mut() {
+    let mut v = InlineVec::<u32, 32>::new();
+    v.push(1);
+    let spare_bytes = (v.capacity() - v.len()) * 4;
+    for align in [1, 4, 16, 32] {
+        let (pad, aligned) = v.aligned_spare_capacity_mut(align);
+        assert_eq!(aligned.as_ptr() as usize % align, 0);
+        assert_eq!(pad.len() % 4, 0);
+        assert_eq!(pad.len() + aligned.len() * 4, spare_bytes);
+    }
+    let (pad, _) = v.aligned_spare_capacity_mut(4);
+    assert!(pad.is_empty());
+}
+
+#[test]
+fn vec_try_append() {
+    let mut a = InlineVec::<usize, 4>::from_iter([1, 2]);
+    let mut b = InlineVec::<usize, 4>::from_iter([3, 4, 5]);
+    assert!(a.try_append(&mut b).is_err());
+    assert_eq!(a, &[1, 2]);
+    assert_eq!(b, &[3, 4, 5]);
+
+    b.pop();
+    assert!(a.try_append(&mut b).is_ok());
+    assert_eq!(a, &[1, 2, 3, 4]);
+    assert!(b.is_empty());
+
+    let mut c = InlineVec::<usize, 4>::new();
+    assert!(c.try_append(&mut a).is_ok());
+    assert_eq!(c, &[1, 2, 3, 4]);
+    assert!(a.is_empty());
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<4>>)]
+fn vec_extend_within_capacity<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::new();
+    v.reserve_exact(C::Index::from_usize(4));
+    let cap = v.capacity().to_usize();
+    let mut rest = v.extend_within_capacity(0..cap + 2);
+    assert_eq!(v.len().to_usize(), cap);
+    assert_eq!(v.capacity().to_usize(), cap);
+    assert_eq!(rest.next(), Some(cap));
+    assert_eq!(rest.next(), Some(cap + 1));
+    assert_eq!(rest.next(), None);
+
+    let mut rest = v.extend_within_capacity([99]);
+    assert_eq!(rest.next(), Some(99));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_auto_shrink() {
+    let mut v = FlexVec::<usize, AutoShrink<Global, 4>>::new();
+    v.extend(0..100);
+    assert!(v.capacity() >= 100);
+    v.truncate(10);
+    assert!(v.capacity() >= 100);
+    v.clear();
+    assert_eq!(v.capacity(), 4);
+    v.extend(0..4);
+    v.truncate(0);
+    assert_eq!(v.capacity(), 4);
+
+    let mut v = FlexVec::<usize, AutoShrink<Inline<10>, 4>>::new();
+    v.extend(0..10);
+    v.clear();
+    assert_eq!(v.capacity(), 10);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_auto_shrink_emptied() {
+    type ShrinkVec = FlexVec<usize, AutoShrink<Global, 4>>;
+    let filled = || ShrinkVec::from_iter(0..100);
+
+    let mut v = filled();
+    v.drain(..10);
+    assert!(v.capacity() >= 100);
+    v.drain(..);
+    assert_eq!(v.capacity(), 4);
+
+    let mut v = filled();
+    v.retain(|i| *i > 50);
+    assert!(v.capacity() >= 100);
+    v.retain(|_| false);
+    assert_eq!(v.capacity(), 4);
+
+    let mut v = filled();
+    v.retain_rev(|_| false);
+    assert_eq!(v.capacity(), 4);
+
+    let mut v = filled();
+    v.retain_range(.., |_| false);
+    assert_eq!(v.capacity(), 4);
+
+    let mut v = filled();
+    assert_eq!(v.try_retain(|_| Ok::<_, ()>(false)), Ok(()));
+    assert_eq!(v.capacity(), 4);
+
+    let mut v = filled();
+    assert_eq!(v.extract_if(|_| true).count(), 100);
+    assert_eq!(v.capacity(), 4);
+
+    let mut v = filled();
+    let rest = v.split_off(0);
+    assert_eq!(rest.len(), 100);
+    assert_eq!(v.capacity(), 4);
+
+    let mut v = filled();
+    let mut target = ShrinkVec::new();
+    v.split_off_into(0, &mut target);
+    assert_eq!(target.len(), 100);
+    assert_eq!(v.capacity(), 4);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_ord() {
+    use core::cmp::Ordering;
+
+    let a = FlexVec::<u8>::from_slice(&[1, 2, 3]);
+    let b = FlexVec::<u8, Inline<5>>::from_slice(&[1, 2, 4]);
+    let c = FlexVec::<u8>::from_slice(&[1, 2]);
+    assert_eq!(a.partial_cmp(&b), Some(Ordering::Less));
+    assert_eq!(b.partial_cmp(&a), Some(Ordering::Greater));
+    assert!(c < a);
+    assert_eq!(a.cmp(&a.clone()), Ordering::Equal);
+
+    let mut nested = FlexVec::<FlexVec<u8>>::new();
+    nested.extend([a.clone(), c.clone(), FlexVec::new()]);
+    nested.sort();
+    assert_eq!(nested, [FlexVec::new(), c, a]);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<20>>)]
+fn vec_writer_backpatch<C: VecConfigNew<u8>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<u8, C>::new();
+    v.push(0xff);
+    let mut w = VecWriter::new(&mut v);
+    let len_pos = w.position();
+    w.write_slice(&[0, 0]);
+    w.write_slice(b"body");
+    let body_len = w.position().to_usize() - len_pos.to_usize() - 2;
+    w.overwrite_at(len_pos, &(body_len as u16).to_be_bytes());
+    assert_eq!(w.position().to_usize(), 7);
+    w.seek(C::Index::from_usize(3));
+    w.write_slice(b"BODY!");
+    assert_eq!(w.into_inner().as_slice(), b"\xff\x00\x04BODY!");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn vec_writer_io() {
+    use std::io::Write;
+
+    let mut v = FlexVec::<u8, Inline<8>>::new();
+    let mut w = VecWriter::new(&mut v);
+    w.write_all(b"\0\0abc").unwrap();
+    w.seek(0);
+    w.write_all(&3u16.to_le_bytes()).unwrap();
+    assert_eq!(w.position(), 2);
+    w.seek(5);
+    assert_eq!(w.write(b"defghij").unwrap(), 3);
+    assert_eq!(v.as_slice(), b"\x03\x00abcdef");
+}
+
+#[test]
+fn vec_position_max_min() {
+    let v = InlineVec::<(u8, usize), 6>::new();
+    assert_eq!(v.position_max(), None);
+    assert_eq!(v.position_min(), None);
+
+    let v = InlineVec::<u8, 6>::from_slice(&[3, 1, 4, 1, 4, 2]);
+    assert_eq!(v.position_max(), Some(4));
+    assert_eq!(v.position_min(), Some(1));
+
+    // tie-breaking matches the iterator adapters
+    let v = InlineVec::<Ordered, 4>::from_slice(&[
+        Ordered(1, 0),
+        Ordered(5, 1),
+        Ordered(1, 2),
+        Ordered(5, 3),
+    ]);
+    assert_eq!(v.iter().max().unwrap().1, v.position_max().unwrap());
+    assert_eq!(v.iter().min().unwrap().1, v.position_min().unwrap());
+
+    #[derive(Debug, Clone)]
+    struct Ordered(u8, usize);
+
+    impl PartialEq for Ordered {
+        fn eq(&self, other: &Self) -> bool {
+            self.0 == other.0
+        }
+    }
+
+    impl Eq for Ordered {}
+
+    impl PartialOrd for Ordered {
+        fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+            Some(self.cmp(other))
+        }
+    }
+
+    impl Ord for Ordered {
+        fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+            self.0.cmp(&other.0)
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_grow_factor() {
+    use flex_alloc::index::{Grow, GrowFactor};
+
+    type Grow15 = GrowFactor<3, 2>;
+    assert!(Grow15::next_capacity::<u8, usize>(0, 1) >= 1);
+    assert_eq!(Grow15::next_capacity::<u8, usize>(1, 2), 2);
+    assert_eq!(Grow15::next_capacity::<u8, usize>(10, 11), 15);
+    assert_eq!(Grow15::next_capacity::<u8, usize>(10, 20), 20);
+    assert_eq!(Grow15::next_capacity::<u8, usize>(usize::MAX / 2, 100), 100);
+    assert_eq!(Grow15::next_capacity::<u8, u8>(200, 201), 201);
+
+    let mut v = FlexVec::<u8, Custom<Global, usize, Grow15>>::with_capacity(10);
+    v.extend([0; 10]);
+    assert_eq!(v.capacity(), 10);
+    v.push(1);
+    assert_eq!(v.capacity(), 15);
+}
+
+#[test]
+fn index_conversions() {
+    assert_eq!(u16::try_from_usize(65535), Some(u16::MAX));
+    assert_eq!(u16::try_from_usize(65536), None);
+    assert_eq!(u32::MAX_USIZE as u64, u32::MAX as u64);
+    assert_eq!(u64::MAX_USIZE, usize::MAX);
+    assert_eq!(u64::from_usize(10).to_usize(), 10);
+    assert_eq!(u16::from_usize(65000).saturating_add(1000), u16::MAX);
+    assert_eq!(u32::from_usize(5).saturating_sub(10), 0);
+}
+
+#[test]
+fn index_checked_arithmetic() {
+    fn add<I: Index>(a: I, b: I) -> Option<I> {
+        a.checked_add(b)
+    }
+    fn sub<I: Index>(a: I, b: I) -> Option<I> {
+        a.checked_sub(b)
+    }
+    assert_eq!(add(250u8, 5), Some(255));
+    assert_eq!(add(250u8, 6), None);
+    assert_eq!(add(u64::MAX - 1, 1), Some(u64::MAX));
+    assert_eq!(add(usize::MAX, 1), None);
+    assert_eq!(sub(5u16, 5), Some(0));
+    assert_eq!(sub(5u32, 6), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_index_types() {
+    let mut v = FlexVec::<u8, Custom<Global, u16>>::new();
+    v.extend([1, 2, 3]);
+    assert_eq!(v.len(), 3u16);
+    assert_eq!(
+        v.try_reserve(u16::MAX),
+        Err(StorageError::IndexOverflow {
+            requested: u16::MAX as usize + 3,
+            max: u16::MAX as usize
+        })
+    );
+    v.truncate(1);
+    assert_eq!(v, &[1]);
+
+    let mut v = FlexVec::<u8, Custom<Global, u32>>::with_capacity(10);
+    v.insert(0, 1);
+    assert_eq!(v.capacity(), 10u32);
+
+    let mut v = FlexVec::<u8, Custom<Global, u64>>::new();
+    v.extend_from_slice(&[1, 2, 3]);
+    assert_eq!(v.remove(1), 2);
+    assert_eq!(v, &[1, 3]);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_partition_dedup<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from([1, 2, 2, 3, 3, 2, 1, 1]);
+    let (dedup, dups) = v.partition_dedup();
+    assert_eq!(dedup, &[1, 2, 3, 2, 1]);
+    dups.sort();
+    assert_eq!(dups, &[1, 2, 3]);
+    assert_eq!(v.len().to_usize(), 8);
+
+    let mut v = FlexVec::<usize, C>::from([1]);
+    let (dedup, dups) = v.partition_dedup_by(|a, b| a == b);
+    assert_eq!(dedup, &[1]);
+    assert!(dups.is_empty());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_grow_doubling_capped() {
+    use flex_alloc::index::GrowDoublingCapped;
+
+    let mut v = FlexVec::<u32, Custom<Global, usize, GrowDoublingCapped<10>>>::new();
+    for i in 0..10 {
+        v.push(i);
+        assert!(v.capacity() <= 10);
+    }
+    assert_eq!(v.capacity(), 10);
+    assert_eq!(
+        v.try_push(10).map_err(|e| e.error().clone()),
+        Err(StorageError::CapacityLimit)
+    );
+    assert_eq!(v.len(), 10);
+    assert_eq!(v.try_reserve_exact(1), Err(StorageError::CapacityLimit));
+    assert_eq!(
+        FlexVec::<u32, Custom<Global, usize, GrowDoublingCapped<10>>>::try_with_capacity(11)
+            .map(|_| ()),
+        Err(StorageError::CapacityLimit)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_from_iter_exact_hint() {
+    let v: FlexVec<usize> = (0..1000).map(|i| i * 2).collect();
+    assert_eq!(v.capacity(), 1000);
+    assert_eq!(v.len(), 1000);
+
+    let alloc = TestAlloc::default();
+    let v = FlexVec::from_iter_in((0..1000).map(|i| i * 2), &alloc);
+    assert_eq!(v.capacity(), 1000);
+    assert_eq!(v[999], 1998);
+    assert_eq!(alloc.calls(), 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn vec_fill_from_reader() {
+    let data: std::vec::Vec<u8> = (0..200).collect();
+
+    let mut v = FlexVec::<u8>::from_slice(&[255]);
+    let count = v.fill_from_reader(&mut data.as_slice()).unwrap();
+    assert_eq!(count, 200);
+    assert_eq!(v[0], 255);
+    assert_eq!(&v[1..], data.as_slice());
+
+    let mut v = InlineVec::<u8, 50>::new();
+    let mut reader = data.as_slice();
+    let count = v.fill_from_reader(&mut reader).unwrap();
+    assert_eq!(count, 50);
+    assert_eq!(v, &data[..50]);
+    assert_eq!(reader.len(), 150);
+    assert_eq!(v.fill_from_reader(&mut reader).unwrap(), 0);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn vec_fill_from_reader_small_chunks() {
+    struct Chunked<'r>(&'r [u8]);
+
+    impl std::io::Read for Chunked<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            assert!(buf.iter().all(|b| *b == 0));
+            let count = buf.len().min(self.0.len()).min(3);
+            buf[..count].copy_from_slice(&self.0[..count]);
+            self.0 = &self.0[count..];
+            Ok(count)
+        }
+    }
+
+    let data: std::vec::Vec<u8> = (0..=255).cycle().take(5000).collect();
+    let alloc = CountingAlloc::new(Global);
+    let mut v = FlexVec::new_in(&alloc);
+    let count = v.fill_from_reader(&mut Chunked(&data)).unwrap();
+    assert_eq!(count, 5000);
+    assert_eq!(v, data.as_slice());
+    assert_eq!(alloc.live_allocations(), 1);
+
+    // capacity grows geometrically, even when exact growth is configured
+    let mut v = FlexVec::<u8, Custom<Global, usize, flex_alloc::index::GrowExact>>::new();
+    v.fill_from_reader(&mut Chunked(&data)).unwrap();
+    assert_eq!(v, data.as_slice());
+    assert_eq!(v.capacity(), 8192);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_drain_and_shrink() {
+    let mut v = FlexVec::<usize>::from_iter(0..1000);
+    let mut drain = v.drain_and_shrink(5..995);
+    assert_eq!(drain.next(), Some(5));
+    assert_eq!(drain.next_back(), Some(994));
+    drop(drain);
+    assert_eq!(v, &[0, 1, 2, 3, 4, 995, 996, 997, 998, 999]);
+    assert_eq!(v.capacity(), 10);
+
+    let mut v = FlexVec::<usize>::from_iter(0..10);
+    v.drain_and_shrink(..5);
+    assert_eq!(v, &[5, 6, 7, 8, 9]);
+    assert_eq!(v.capacity(), 10);
+
+    let mut v = ThinVec::<usize>::from_iter(0..10);
+    v.drain_and_shrink(..);
+    assert_eq!(v.capacity(), 0);
+    v.push(1);
+    assert_eq!(v, &[1]);
+
+    let mut v = InlineVec::<usize, 10>::from_iter(0..10);
+    v.drain_and_shrink(..);
+    assert!(v.is_empty());
+    assert_eq!(v.capacity(), 10);
+}
+
+#[test]
+fn vec_into_fixed_storage() {
+    use flex_alloc::vec::FixedVec;
+    use std::rc::Rc;
+
+    let mut buf = array_storage::<Rc<usize>, 4>();
+    let item = Rc::new(1usize);
+    let mut v = FixedVec::new_in(&mut buf);
+    v.extend([item.clone(), item.clone()]);
+    assert_eq!(Rc::strong_count(&item), 3);
+    let storage = v.into_fixed_storage();
+    assert_eq!(storage.len(), 4);
+    assert_eq!(Rc::strong_count(&item), 1);
+
+    let mut v = FixedVec::from_fixed_storage(storage);
+    v.extend([item.clone(), item.clone(), item.clone(), item.clone()]);
+    assert_eq!(v.capacity(), 4);
+    assert_eq!(
+        v.try_push(item.clone()).map_err(|e| e.error().clone()),
+        Err(flex_alloc::StorageError::CapacityLimit)
+    );
+    let storage = v.into_fixed_storage();
+    assert_eq!(storage.len(), 4);
+    assert_eq!(Rc::strong_count(&item), 1);
+}
+
+#[test]
+fn vec_from_full_array() {
+    const TABLE: InlineVec<u8, 4> = InlineVec::from_full_array([1, 2, 3, 4]);
+    assert_eq!(TABLE, &[1, 2, 3, 4]);
+    assert_eq!(TABLE.capacity(), 4);
+
+    let item = std::rc::Rc::new(1usize);
+    let v = InlineVec::from_full_array([item.clone(), item.clone()]);
+    assert_eq!(v.len(), 2);
+    assert_eq!(std::rc::Rc::strong_count(&item), 3);
+    drop(v);
+    assert_eq!(std::rc::Rc::strong_count(&item), 1);
+
+    let storage = flex_alloc::storage::ArrayStorage::from_array([5u32, 6]);
+    assert_eq!(unsafe { storage.0[1].assume_init() }, 6);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_extend_sorted_unique<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from([1, 3, 5]);
+    v.extend_sorted_unique([2, 3, 6]);
+    assert_eq!(v, &[1, 2, 3, 5, 6]);
+    v.extend_sorted_unique([0, 0, 4, 6, 7, 7]);
+    assert_eq!(v, &[0, 1, 2, 3, 4, 5, 6, 7]);
+
+    let mut v = FlexVec::<usize, C>::new();
+    v.extend_sorted_unique([1, 1, 2]);
+    assert_eq!(v, &[1, 2]);
+}
+
+#[test]
+fn vec_extend_sorted_unique_full() {
+    // no spare capacity is available for merging
+    let mut v = InlineVec::<usize, 4>::from([1, 3, 5]);
+    v.extend_sorted_unique([3, 4]);
+    assert_eq!(v, &[1, 3, 4, 5]);
+    v.extend_sorted_unique([1, 5]);
+    assert_eq!(v, &[1, 3, 4, 5]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_extend_sorted_unique_merge() {
+    use std::collections::BTreeSet;
+    use std::rc::Rc;
+
+    let base: std::vec::Vec<_> = (0..500).map(|i| Rc::new(i * 3)).collect();
+    let input: std::vec::Vec<_> = (0..1000).map(|i| Rc::new(i * 2)).collect();
+    let mut v = FlexVec::<Rc<usize>>::from_slice(&base);
+    v.extend_sorted_unique(input.iter().cloned());
+    let expect: BTreeSet<usize> = (0..500)
+        .map(|i| i * 3)
+        .chain((0..1000).map(|i| i * 2))
+        .collect();
+    assert!(v.iter().map(|i| **i).eq(expect.iter().copied()));
+    // duplicates are dropped in favor of the existing items
+    assert!(base.iter().all(|i| Rc::strong_count(i) == 2));
+    assert!(input
+        .iter()
+        .all(|i| Rc::strong_count(i) == if **i % 3 == 0 && **i < 1500 { 1 } else { 2 }));
+    drop(v);
+    assert!(input.iter().chain(&base).all(|i| Rc::strong_count(i) == 1));
+}
+
+#[test]
+fn vec_extend_sorted_unique_unsorted() {
+    // the result is unspecified, but all items are retained or dropped
+    let mut v = InlineVec::<usize, 20>::from([5, 1, 3]);
+    v.extend_sorted_unique([9, 2, 2, 0, 7]);
+    assert!(v.len() >= 3 && v.len() <= 8);
+}
+
+#[cfg(all(feature = "std", unix))]
+#[test]
+fn vec_with_capacity_in_touched() {
+    let mut v = FlexVec::<u64, _>::with_capacity_in_touched(100_000, Global);
+    assert!(v.is_empty());
+    assert!(v.capacity() >= 100_000);
+    v.extend(0..100_000);
+    assert_eq!(v[99_999], 99_999);
+}
+
+#[cfg(all(feature = "std", unix))]
+#[test]
+fn vec_with_capacity_in_touched_pages() {
+    /// An allocator which fills new allocations with a marker byte.
+    #[derive(Debug)]
+    struct MarkedAlloc;
+
+    impl RawAlloc for MarkedAlloc {
+        fn try_alloc(&self, layout: Layout) -> Result<NonNull<[u8]>, StorageError> {
+            let ptr = Global.try_alloc(layout)?;
+            unsafe { ptr.cast::<u8>().as_ptr().write_bytes(0xAA, ptr.len()) };
+            Ok(ptr)
+        }
+
+        unsafe fn release(&self, ptr: NonNull<u8>, layout: Layout) {
+            Global.release(ptr, layout)
+        }
+    }
+
+    let size = 3 * 4096 + 100;
+    let mut v = FlexVec::<u8, _>::with_capacity_in_touched(size, MarkedAlloc);
+    assert!(v.is_empty());
+    let spare = v.spare_capacity_mut();
+    let bytes = unsafe { &*(spare as *const [_] as *const [u8]) };
+    for (idx, b) in bytes.iter().enumerate() {
+        let touched = idx % 4096 == 0 || idx == bytes.len() - 1;
+        assert_eq!(*b, if touched { 0 } else { 0xAA }, "byte {idx}");
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_spilled() {
+    let mut buf = array_storage::<usize, 4>();
+    let mut v = FlexVec::new_in(buf.with_alloc());
+    assert!(!v.spilled());
+    v.extend([1, 2, 3, 4]);
+    assert!(!v.spilled());
+    v.push(5);
+    assert!(v.spilled());
+    v.truncate(1);
+    v.shrink_to_fit();
+    assert!(v.spilled());
+
+    let mut buf = byte_storage::<16>();
+    let v = FlexVec::<u8, _>::with_capacity_in(32, buf.with_alloc());
+    assert!(v.spilled());
+}
+
+#[test]
+fn byte_storage_capacity_for() {
+    use flex_alloc::storage::ByteStorage;
+
+    const CAP: usize = ByteStorage::<u8, 64>::capacity_for::<u32>();
+    assert_eq!(CAP, 15);
+    assert_eq!(ByteStorage::<u32, 64>::capacity_for::<u32>(), 16);
+    assert_eq!(ByteStorage::<u64, 64>::capacity_for::<u16>(), 32);
+    assert_eq!(ByteStorage::<u8, 2>::capacity_for::<u32>(), 0);
+
+    let mut buf = byte_storage::<64>();
+    let v = FlexVec::<u32, _>::new_in(&mut buf);
+    assert!(v.capacity() >= CAP);
+    let mut buf = aligned_byte_storage::<u32, 64>();
+    let v = FlexVec::<u32, _>::new_in(&mut buf);
+    assert_eq!(v.capacity(), 16);
+}
+
+#[test]
+fn vec_as_chunks() {
+    let mut v = InlineVec::<u8, 8>::from_slice(&[1, 2, 3, 4, 5, 6, 7]);
+    let (chunks, rem) = v.as_chunks::<3>();
+    assert_eq!(chunks, &[[1, 2, 3], [4, 5, 6]]);
+    assert_eq!(rem, &[7]);
+
+    let (chunks, rem) = v.as_chunks_mut::<2>();
+    assert_eq!(chunks.len(), 3);
+    chunks[1] = [0, 0];
+    rem[0] = 9;
+    assert_eq!(v, &[1, 2, 0, 0, 5, 6, 9]);
+
+    let (chunks, rem) = v.as_chunks::<8>();
+    assert!(chunks.is_empty());
+    assert_eq!(rem.len(), 7);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_first_chunk<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::new();
+    assert_eq!(v.first_chunk::<0>(), Some(&[]));
+    assert_eq!(v.first_chunk::<1>(), None);
+    v.extend_from_slice(SLICE);
+    assert_eq!(v.first_chunk::<3>(), Some(&[1, 2, 3]));
+    assert_eq!(v.first_chunk::<5>(), Some(&[1, 2, 3, 4, 5]));
+    assert_eq!(v.first_chunk::<6>(), None);
+    v.first_chunk_mut::<2>().unwrap()[1] = 10;
+    assert!(v.first_chunk_mut::<6>().is_none());
+    assert_eq!(v, &[1, 10, 3, 4, 5]);
+}
+
+#[test]
+#[should_panic]
+fn vec_as_chunks_zero() {
+    let v = InlineVec::<u8, 8>::from_slice(&[1, 2, 3]);
+    let _ = v.as_chunks::<0>();
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_spare_capacity<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::new();
+    assert_eq!(v.capacity_remaining(), v.capacity());
+    assert_eq!(v.spare_capacity().len(), v.capacity().to_usize());
+    v.extend([1, 2, 3]);
+    let remain = v.capacity().to_usize() - 3;
+    assert_eq!(v.capacity_remaining().to_usize(), remain);
+    assert_eq!(v.spare_capacity().len(), remain);
+    v.reserve(C::Index::from_usize(1));
+    v.spare_capacity_mut()[0].write(4);
+    unsafe { v.set_len(C::Index::from_usize(4)) };
+    assert_eq!(v, &[1, 2, 3, 4]);
+}
+
+#[test]
+fn vec_is_full_remaining() {
+    let mut v = InlineVec::<usize, 3>::new();
+    assert!(!v.is_full());
+    assert_eq!(v.capacity_remaining(), 3);
+    while v.push_within_capacity(1).is_ok() {}
+    assert!(v.is_full());
+    assert_eq!(v.capacity_remaining(), 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_is_full_remaining_zst() {
+    let mut z = FlexVec::<()>::with_capacity(6);
+    z.extend([(), (), (), ()]);
+    assert!(!z.is_full());
+    assert_eq!(z.capacity_remaining(), z.capacity() - 4);
+    z.shrink_to_fit();
+    assert!(z.is_full());
+    assert_eq!(z.capacity_remaining(), 0);
+}
+
+#[test]
+fn vec_reserve_for_extend() {
+    let mut v = InlineVec::<usize, 10>::new();
+    let iter = (0..20).filter(|i| i % 2 == 0);
+    assert_eq!(
+        v.try_reserve_for_extend(&iter),
+        Err(flex_alloc::StorageError::CapacityLimit)
+    );
+    let iter = (0..10).filter(|i| i % 2 == 0);
+    v.reserve_for_extend(&iter);
+    for i in iter {
+        v.push_within_capacity(i).unwrap();
+    }
+    assert_eq!(v, &[0, 2, 4, 6, 8]);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_as_from_bytes<C: VecConfigNew<u16>>(#[case] _config: Cfg<C>) {
+    let v = FlexVec::<u16, C>::from_slice(&[1, 0x0302]);
+    assert_eq!(
+        v.as_bytes(),
+        &[1u16.to_ne_bytes(), 0x0302u16.to_ne_bytes()].concat()
+    );
+    // unaligned source data is supported
+    let (a, b) = (1u16.to_ne_bytes(), 0x0302u16.to_ne_bytes());
+    let bytes = [0u8, a[0], a[1], b[0], b[1]];
+    let w = FlexVec::<u16, C>::from_bytes(&bytes[1..]).unwrap();
+    assert_eq!(w, v);
+    assert!(FlexVec::<u16, C>::from_bytes(&bytes).is_none());
+    assert!(FlexVec::<u16, C>::from_bytes(&[]).unwrap().is_empty());
+}
+
+#[test]
+fn vec_from_bytes_zst() {
+    assert!(InlineVec::<[u8; 0], 1>::from_bytes(&[]).unwrap().is_empty());
+    assert!(InlineVec::<[u8; 0], 1>::from_bytes(&[1]).is_none());
+    assert_eq!(
+        InlineVec::<u32, 1>::try_from_bytes(&[0; 8]).err(),
+        Some(flex_alloc::StorageError::CapacityLimit)
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_reserve_for_extend_alloc() {
+    let mut v = FlexVec::<usize>::new();
+    let iter = (0..16).filter(|i| i % 3 == 0);
+    v.reserve_for_extend(&iter);
+    let cap = v.capacity();
+    assert!(cap >= 16);
+    v.extend(iter);
+    assert_eq!(v, &[0, 3, 6, 9, 12, 15]);
+    assert_eq!(v.capacity(), cap);
+}
+
+#[test]
+fn vec_inline_static() {
+    static TABLE: InlineVec<u32, 8> = InlineVec::new();
+    const FULL: InlineVec<u32, 2> = InlineVec::from_full_array([1, 2]);
+    assert!(TABLE.is_empty());
+    assert_eq!(TABLE.capacity(), 8);
+    assert_eq!(FULL, &[1, 2]);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_swap_reverse<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from([1, 2, 3, 4]);
+    v.swap(C::Index::from_usize(0), C::Index::from_usize(3));
+    assert_eq!(v, &[4, 2, 3, 1]);
+    v.reverse();
+    assert_eq!(v, &[1, 3, 2, 4]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid element index")]
+fn vec_swap_out_of_bounds() {
+    let mut v = InlineVec::<usize, 4>::from_slice(&[1, 2]);
+    v.swap(0, 2);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_shrink_realloc_global() {
+    // the system allocator may or may not shrink the allocation in place
+    let mut v = FlexVec::<usize>::with_capacity(10000);
+    v.extend(0..100);
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 100);
+    assert!(v.iter().copied().eq(0..100));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_shrink_in_place_bump() {
+    let bump = Bump::new();
+    let mut v = FlexVec::<usize, _>::with_capacity_in(100, &bump);
+    v.extend(0..10);
+    let ptr = v.as_ptr();
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 10);
+    assert_eq!(v.as_ptr(), ptr);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_aligned_alloc() {
+    use flex_alloc::storage::AlignedAlloc;
+
+    let mut v = FlexVec::<f32, Custom<AlignedAlloc<Global, 64>, usize>>::new();
+    v.push(1.0);
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+    v.extend((0..100).map(|i| i as f32));
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+    v.truncate(3);
+    v.shrink_to_fit();
+    assert_eq!(v.as_ptr() as usize % 64, 0);
+    assert_eq!(v, &[1.0, 0.0, 1.0]);
+
+    let alloc = CountingAlloc::new(Global);
+    let mut v = FlexVec::<u8, _>::with_capacity_in(1, AlignedAlloc::<_, 16>(&alloc));
+    assert_eq!(v.as_ptr() as usize % 16, 0);
+    v.push(1);
+    assert_eq!(alloc.live_allocations(), 1);
+    drop(v);
+    assert_eq!(alloc.live_allocations(), 0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_counting_alloc() {
+    let mut v = FlexVec::<usize, Custom<CountingAlloc<Global>, usize>>::new();
+    assert_eq!(v.allocator().live_allocations(), 0);
+    v.reserve_exact(4);
+    assert_eq!(v.allocator().live_allocations(), 1);
+    assert_eq!(v.allocator().allocated_bytes(), 4 * size_of::<usize>());
+    v.extend(0..8);
+    assert_eq!(v.allocator().live_allocations(), 1);
+    assert_eq!(v.allocator().allocated_bytes(), size_of_val(v.as_slice()));
+    v.clear();
+    v.shrink_to_fit();
+    assert_eq!(v.allocator().live_allocations(), 0);
+    assert_eq!(v.allocator().allocated_bytes(), 0);
+
+    let alloc = CountingAlloc::new(Global);
+    let a = FlexVec::<usize, _>::from_slice_in(SLICE, &alloc);
+    let b = a.clone();
+    assert_eq!(alloc.live_allocations(), 2);
+    drop(a);
+    drop(b);
+    assert_eq!(alloc.live_allocations(), 0);
+    assert_eq!(alloc.allocated_bytes(), 0);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_drain_consume_prefix<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from_slice(SLICE);
+    let drained: Vec<usize> = v.drain_prefix(C::Index::from_usize(2)).collect();
+    assert_eq!(drained, &[1, 2]);
+    assert_eq!(v, &[3, 4, 5]);
+    v.consume_prefix(C::Index::from_usize(1));
+    assert_eq!(v, &[4, 5]);
+    v.consume_prefix(C::Index::ZERO);
+    assert_eq!(v, &[4, 5]);
+    v.consume_prefix(C::Index::from_usize(5));
+    assert!(v.is_empty());
+
+    let mut v = FlexVec::<usize, C>::from_slice(SLICE);
+    assert_eq!(v.drain_prefix(C::Index::from_usize(8)).count(), 5);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn vec_consume_prefix_drops() {
+    use std::rc::Rc;
+    let item = Rc::new(());
+    let mut v = InlineVec::<Rc<()>, 10>::from_iter((0..5).map(|_| item.clone()));
+    v.consume_prefix(3);
+    assert_eq!(Rc::strong_count(&item), 3);
+    drop(v);
+    assert_eq!(Rc::strong_count(&item), 1);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_index_overflow_error() {
+    let mut v = FlexVec::<u8, Custom<Global, u8>>::new();
+    let err = v.try_extend_from_slice(&[0; 256]).unwrap_err();
+    assert_eq!(
+        err,
+        StorageError::IndexOverflow {
+            requested: 256,
+            max: 255
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Exceeded index capacity: requested 256, maximum 255"
+    );
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<5>>)]
+fn vec_assign_from_slice<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::with_capacity(C::Index::from_usize(5));
+    let capacity = v.capacity();
+    v.extend_from_slice(&[9, 9]);
+    v.assign_from_slice(SLICE);
+    assert_eq!(v, SLICE);
+    v.assign_from_slice(&[1]);
+    assert_eq!(v, &[1]);
+    assert_eq!(
+        v.try_assign_from_slice(&[0; 6]),
+        Err(flex_alloc::StorageError::CapacityLimit)
+    );
+    assert_eq!(v, &[1]);
+    assert_eq!(v.capacity(), capacity);
+}
+
+#[test]
+fn vec_assign_from_slice_fixed() {
+    let mut buf = array_storage::<String, 3>();
+    let mut v = FlexVec::new_in(&mut buf);
+    v.try_assign_from_slice(&["a".to_string(), "b".to_string()])
+        .expect("assign failed");
+    assert_eq!(v, &["a", "b"]);
+    let long = ["c", "d", "e", "f"].map(String::from);
+    assert!(v.try_assign_from_slice(&long).is_err());
+    assert_eq!(v, &["a", "b"]);
+    v.assign_from_slice(&[]);
+    assert!(v.is_empty());
+}
+
+#[test]
+fn vec_slice_clone_from_slice() {
+    let mut v = InlineVec::<usize, 5>::from_slice(&[1, 2, 3]);
+    v.clone_from_slice(&[4, 5, 6]);
+    assert_eq!(v, &[4, 5, 6]);
+}
+
+#[test]
+fn vec_inline_small() {
+    assert!(size_of::<InlineSmallVec<u8, 7>>() < size_of::<InlineVec<u8, 7>>());
+    assert_eq!(size_of::<InlineSmallVec<u8, 7>>(), 8);
+
+    let mut v = InlineSmallVec::<u8, 7>::new();
+    v.extend_from_slice(&[1, 2, 3]);
+    v.insert(0, 0);
+    assert_eq!(v, &[0, 1, 2, 3]);
+    assert_eq!(v.capacity(), 7);
+    assert_eq!(v.try_extend_from_slice(&[0; 3]).map(|_| v.len()), Ok(7));
+    assert!(v.try_push(8).is_err());
+
+    let v = InlineSmallVec::<u16, 300, u16>::from_iter(0..300);
+    assert_eq!(v.len(), 300);
+    assert_eq!(v.clone()[299], 299);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_binary_search<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let v = FlexVec::<usize, C>::from_slice(&[1, 3, 5, 7]);
+    assert_eq!(v.binary_search(&5), Ok(C::Index::from_usize(2)));
+    assert_eq!(v.binary_search(&0), Err(C::Index::ZERO));
+    assert_eq!(v.binary_search(&8), Err(C::Index::from_usize(4)));
+    assert_eq!(
+        v.binary_search_by(|item| item.cmp(&4)),
+        Err(C::Index::from_usize(2))
+    );
+    assert_eq!(
+        v.binary_search_by_key(&14, |item| item * 2),
+        Ok(C::Index::from_usize(3))
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_binary_search_borrowed_key() {
+    let v = FlexVec::<String>::from_iter(["a", "b", "d"].map(String::from));
+    assert_eq!(v.binary_search_by_key(&"b", |s| s.as_str()), Ok(1));
+    assert_eq!(v.binary_search_by_key(&"c", |s| s.as_str()), Err(2));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_thin_boxed_slice() {
+    let mut v = ThinVec::<String>::with_capacity(10);
+    v.extend(["a", "b", "c"].map(String::from));
+    let boxed = v.into_boxed_slice();
+    assert_eq!(&*boxed, &["a", "b", "c"]);
+
+    let v = ThinVec::from_iter(boxed.into_vec());
+    let boxed: Box<[String]> = v.try_into_boxed_slice().expect("error converting");
+    assert_eq!(&*boxed, &["a", "b", "c"]);
+
+    let boxed: Box<[String]> = ThinVec::<String>::new().into();
+    assert!(boxed.is_empty());
+    let boxed = ThinVec::<String>::with_capacity(4).into_boxed_slice();
+    assert!(boxed.is_empty());
+    let boxed = ThinVec::from_iter([(), ()]).into_boxed_slice();
+    assert_eq!(boxed.len(), 2);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_chunks_mut<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from_slice(SLICE);
+    for chunk in v.chunks_mut(C::Index::from_usize(2)) {
+        chunk.reverse();
+    }
+    assert_eq!(v, &[2, 1, 4, 3, 5]);
+    let lens: Vec<usize> = v
+        .chunks_mut(C::Index::from_usize(3))
+        .map(|chunk| chunk.len())
+        .collect();
+    assert_eq!(lens, &[3, 2]);
+}
+
+#[test]
+#[should_panic(expected = "chunk size must be non-zero")]
+fn vec_chunks_mut_zero() {
+    let mut v = InlineVec::<u8, 8>::from_slice(&[1, 2, 3]);
+    let _ = v.chunks_mut(0);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_bump_alloc() {
+    let counter = CountingAlloc::new(Global);
+    let bump = Bump::with_block_size_in(256, &counter);
+    let mut vecs = Vec::new();
+    for i in 0..100usize {
+        let mut v = FlexVec::<usize, _>::new_in(&bump);
+        v.extend(0..i % 10);
+        v.push(i);
+        vecs.push(v);
+    }
+    for (i, v) in vecs.iter().enumerate() {
+        assert_eq!(v.len(), i % 10 + 1);
+        assert_eq!(v[v.len() - 1], i);
+    }
+    assert!(bump.block_count() > 1);
+    assert_eq!(counter.live_allocations(), bump.block_count());
+    drop(vecs);
+    drop(bump);
+    assert_eq!(counter.live_allocations(), 0);
+    assert_eq!(counter.allocated_bytes(), 0);
+
+    let mut bump = Bump::new();
+    let mut v = FlexVec::<u8, _>::new_in(&bump);
+    v.extend_from_slice(&[0; 10000]);
+    let mut w = FlexVec::<u64, _>::with_capacity_in(3, &bump);
+    w.extend([1, 2, 3]);
+    assert_eq!(w, &[1, 2, 3]);
+    drop(v);
+    drop(w);
+    bump.reset();
+    assert_eq!(bump.block_count(), 0);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_extract_if<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    let evens: Vec<usize> = v.extract_if(|item| *item % 2 == 0).collect();
+    assert_eq!(evens, &[2, 4, 6, 8]);
+    assert_eq!(v, &[1, 3, 5, 7]);
+
+    // dropping the iterator early retains the unexamined items
+    let mut v = FlexVec::<usize, C>::from_slice(&[1, 2, 3, 4, 5, 6, 7, 8]);
+    let mut iter = v.extract_if(|item| *item % 3 == 0);
+    assert_eq!(iter.next(), Some(3));
+    drop(iter);
+    assert_eq!(v, &[1, 2, 4, 5, 6, 7, 8]);
+
+    let mut v = FlexVec::<usize, C>::new();
+    assert_eq!(v.extract_if(|_| true).count(), 0);
+}
+
+#[test]
+fn vec_extract_if_drops() {
+    use std::rc::Rc;
+    let item = Rc::new(());
+    let mut v = InlineVec::<Rc<()>, 10>::from_iter((0..6).map(|_| item.clone()));
+    let mut index = 0;
+    let removed = v
+        .extract_if(|_| {
+            index += 1;
+            index % 2 == 0
+        })
+        .count();
+    assert_eq!(removed, 3);
+    assert_eq!(v.len(), 3);
+    assert_eq!(Rc::strong_count(&item), 4);
+    drop(v);
+    assert_eq!(Rc::strong_count(&item), 1);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_as_non_null<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::new();
+    let _ = v.as_non_null();
+    v.extend_from_slice(SLICE);
+    let ptr = v.as_non_null();
+    assert_eq!(ptr.as_ptr(), v.as_mut_ptr());
+    assert_eq!(unsafe { *ptr.as_ptr().add(4) }, 5);
+
+    let mut v = InlineVec::<(), 4>::new();
+    v.push(());
+    assert_eq!(v.as_non_null().as_ptr(), v.as_mut_ptr());
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_grow_shrink_capacity() {
+    use flex_alloc::index::{Grow, GrowExact};
+
+    /// Retain capacity in multiples of 8 items when shrinking.
+    #[derive(Debug)]
+    struct GrowBuckets;
+
+    impl Grow for GrowBuckets {
+        fn next_capacity<T, I: Index>(prev: I, minimum: I) -> I {
+            GrowExact::next_capacity::<T, I>(prev, minimum)
+        }
+
+        fn shrink_capacity<T, I: Index>(_current: I, minimum: I) -> I {
+            I::from_usize(minimum.to_usize().div_ceil(8) * 8)
+        }
+    }
+
+    let mut v = FlexVec::<usize, Custom<Global, usize, GrowBuckets>>::with_capacity(40);
+    v.extend(0..10);
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 16);
+    v.shrink_to(12);
+    assert_eq!(v.capacity(), 16);
+    v.truncate(3);
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 8);
+    v.clear();
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 0);
+
+    let mut v = FlexVec::<usize, Custom<Global, usize, GrowBuckets>>::with_capacity(40);
+    v.extend(0..10);
+    v.drain_and_shrink(..7);
+    assert_eq!(v.capacity(), 8);
+
+    let mut v = FlexVec::<usize, AutoShrink<Custom<Global, usize, GrowBuckets>, 3>>::new();
+    v.extend(0..40);
+    v.clear();
+    assert_eq!(v.capacity(), 8);
+
+    let mut v = FlexVec::<usize>::with_capacity(40);
+    v.extend(0..10);
+    v.shrink_to_fit();
+    assert_eq!(v.capacity(), 10);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<20>>)]
+fn vec_insert_from_iter<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from_slice(SLICE);
+    v.insert_from_iter(C::Index::from_usize(2), [10, 11, 12]);
+    assert_eq!(v, &[1, 2, 10, 11, 12, 3, 4, 5]);
+    // unknown size
+    v.insert_from_iter(C::Index::from_usize(1), (20..30).filter(|i| i % 4 == 0));
+    assert_eq!(v, &[1, 20, 24, 28, 2, 10, 11, 12, 3, 4, 5]);
+    v.insert_from_iter(C::Index::from_usize(11), [6]);
+    assert_eq!(v, &[1, 20, 24, 28, 2, 10, 11, 12, 3, 4, 5, 6]);
+    v.insert_from_iter(C::Index::ZERO, []);
+    assert_eq!(v.len().to_usize(), 12);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<20>>)]
+fn vec_insert_slice_middle<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from_slice(SLICE);
+    v.insert_slice(C::Index::from_usize(3), &[7, 8]);
+    assert_eq!(v, &[1, 2, 3, 7, 8, 4, 5]);
+    v.insert_slice(C::Index::from_usize(1), &[9]);
+    assert_eq!(v, &[1, 9, 2, 3, 7, 8, 4, 5]);
+}
+
+#[test]
+#[should_panic(expected = "Invalid element index")]
+fn vec_insert_from_iter_out_of_bounds() {
+    let mut v = InlineVec::<usize, 10>::from_slice(&[1, 2]);
+    v.insert_from_iter(3, [1]);
+}
+
+#[test]
+fn vec_insert_from_iter_inexact() {
+    /// An iterator which under-reports its length.
+    struct Liar(std::ops::Range<usize>, usize);
+
+    impl Iterator for Liar {
+        type Item = usize;
+
+        fn next(&mut self) -> Option<usize> {
+            self.0.next()
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (self.1, Some(self.1))
+        }
+    }
+
+    let mut v = InlineVec::<usize, 20>::from_slice(&[1, 2, 3]);
+    v.insert_from_iter(1, Liar(10..14, 2));
+    assert_eq!(v, &[1, 10, 11, 12, 13, 2, 3]);
+    v.insert_from_iter(1, Liar(20..21, 3));
+    assert_eq!(v, &[1, 20, 10, 11, 12, 13, 2, 3]);
+}
+
+#[test]
+fn vec_insert_slice_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    struct Bomb(Rc<()>, bool);
+
+    impl Clone for Bomb {
+        fn clone(&self) -> Self {
+            if self.1 {
+                panic!("clone failed");
+            }
+            Bomb(self.0.clone(), false)
+        }
+    }
+
+    let item = Rc::new(());
+    let mut v = InlineVec::<Bomb, 10>::from_iter((0..3).map(|_| Bomb(item.clone(), false)));
+    let ins = [Bomb(item.clone(), false), Bomb(item.clone(), true)];
+    let res = catch_unwind(AssertUnwindSafe(|| v.insert_slice(1, &ins)));
+    assert!(res.is_err());
+    assert_eq!(v.len(), 3);
+    drop(ins);
+    assert_eq!(Rc::strong_count(&item), 4);
+    drop(v);
+    assert_eq!(Rc::strong_count(&item), 1);
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_try_from_iter<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let v = FlexVec::<usize, C>::try_from_iter((1..6).filter(|_| true)).expect("error collecting");
+    assert_eq!(v, &[1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn vec_try_from_iter_overflow() {
+    let err = InlineVec::<usize, 4>::try_from_iter(1..=6).expect_err("expected error");
+    assert_eq!(err.error(), &flex_alloc::StorageError::CapacityLimit);
+    let (v, item, rest) = err.into_value();
+    assert_eq!(v, &[1, 2, 3, 4]);
+    assert_eq!(item, 5);
+    assert!(rest.eq([6]));
+
+    let err =
+        InlineVec::<usize, 4>::try_from_iter((1..=6).filter(|_| true)).expect_err("expected error");
+    assert_eq!(err.error(), &flex_alloc::StorageError::CapacityLimit);
+    let (v, item, rest) = err.into_value();
+    // all of the items may be recovered
+    let all: InlineVec<usize, 6> = v.into_iter().chain([item]).chain(rest).collect();
+    assert_eq!(all, &[1, 2, 3, 4, 5, 6]);
+}
+
+#[test]
+fn vec_global_alloc_adapter() {
+    use core::alloc::{GlobalAlloc, Layout};
+    use flex_alloc::storage::GlobalAllocAdapter;
+    use std::alloc::System;
+
+    struct NoAlloc;
+
+    unsafe impl GlobalAlloc for NoAlloc {
+        unsafe fn alloc(&self, _layout: Layout) -> *mut u8 {
+            core::ptr::null_mut()
+        }
+
+        unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {
+            unreachable!();
+        }
+    }
+
+    let mut v = FlexVec::<usize, _>::new_in(GlobalAllocAdapter(System));
+    v.extend(0..100);
+    v.truncate(10);
+    v.shrink_to_fit();
+    assert!(v.iter().copied().eq(0..10));
+
+    let mut z = FlexVec::<(), _>::new_in(GlobalAllocAdapter(NoAlloc));
+    z.extend([(), ()]);
+    assert_eq!(z.len(), 2);
+
+    let mut v = FlexVec::<usize, _>::new_in(GlobalAllocAdapter(NoAlloc));
+    assert_eq!(v.try_reserve(1), Err(flex_alloc::StorageError::AllocError));
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_concat_join<C: VecConfigNew<usize> + VecConfigNew<FlexVec<usize, C>>>(
+    #[case] _config: Cfg<C>,
+) {
+    let mut v = FlexVec::<FlexVec<usize, C>, C>::new();
+    assert!(v.concat_vec().is_empty());
+    assert!(v.join_vec(&0).is_empty());
+    v.push(FlexVec::from_slice(&[1, 2]));
+    v.push(FlexVec::new());
+    v.push(FlexVec::from_slice(&[3]));
+    assert_eq!(v.concat_vec(), &[1, 2, 3]);
+    assert_eq!(v.join_vec(&0), &[1, 2, 0, 0, 3]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_slice_concat_join() {
+    let vv = FlexVec::<FlexVec<u32>>::from_iter([
+        FlexVec::from_slice(&[1, 2]),
+        FlexVec::from_slice(&[3]),
+    ]);
+    let s: std::vec::Vec<u32> = vv.concat();
+    assert_eq!(s, [1, 2, 3]);
+    assert_eq!(vv.join(&[0u32][..]), [1, 2, 0, 3]);
+}
+
+#[test]
+fn vec_inline_from_fn_panic() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+    use std::rc::Rc;
+
+    let item = Rc::new(());
+    let res = catch_unwind(AssertUnwindSafe(|| {
+        InlineVec::<Rc<()>, 5>::from_fn(|i| {
+            if i == 3 {
+                panic!("failed");
+            }
+            item.clone()
+        })
+    }));
+    assert!(res.is_err());
+    assert_eq!(Rc::strong_count(&item), 1);
+}
+
+#[test]
+fn vec_inline_clone_generic() {
+    use flex_alloc::vec::config::VecConfigSpawn;
+
+    fn dup<T: Clone, C: VecConfigSpawn<T>>(v: &FlexVec<T, C>) -> FlexVec<T, C> {
+        v.clone()
+    }
+
+    let v = InlineVec::<String, 4>::from_iter(["a", "b"].map(String::from));
+    let v2 = dup(&v);
+    assert_eq!(v, v2);
+    assert_eq!(v2.capacity(), 4);
+
+    let e = InlineVec::<String, 4>::new();
+    assert!(dup(&e).is_empty());
+}
+
+#[rstest]
+#[cfg_attr(feature="alloc", case::global(Cfg::<Global>))]
+#[cfg_attr(feature="alloc", case::thin(Cfg::<Thin>))]
+#[cfg_attr(feature="alloc", case::custom(Cfg::<Custom<Global, u8>>))]
+#[case::inline(Cfg::<Inline<10>>)]
+fn vec_sort_unstable<C: VecConfigNew<usize>>(#[case] _config: Cfg<C>) {
+    let mut v = FlexVec::<usize, C>::from_slice(&[4, 1, 5, 2, 3]);
+    v.sort_unstable();
+    assert_eq!(v, &[1, 2, 3, 4, 5]);
+    v.sort_unstable_by(|a, b| b.cmp(a));
+    assert_eq!(v, &[5, 4, 3, 2, 1]);
+    v.sort_unstable_by_key(|a| *a % 3);
+    assert_eq!(&v[..2], &[3, 4]);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn vec_sort_stable() {
+    let mut v = InlineVec::<(usize, usize), 10>::from_slice(&[(2, 0), (1, 1), (2, 2), (1, 3)]);
+    v.sort_by_key(|a| a.0);
+    assert_eq!(v, &[(1, 1), (1, 3), (2, 0), (2, 2)]);
+    v.sort_by(|a, b| b.1.cmp(&a.1));
+    assert_eq!(v, &[(1, 3), (2, 2), (1, 1), (2, 0)]);
+    v.sort();
+    assert_eq!(v, &[(1, 1), (1, 3), (2, 0), (2, 2)]);
+}
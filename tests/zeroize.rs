@@ -8,7 +8,7 @@ use core::slice;
 use flex_alloc::{
     storage::{array_storage, byte_storage, Global, RawAlloc, WithAlloc, ZeroizingAlloc},
     vec,
-    vec::{Vec as FlexVec, ZeroizingVec},
+    vec::{config::Custom, Vec as FlexVec, ZeroizingVec, ZeroizingVecIn},
 };
 
 #[derive(Debug)]
@@ -132,3 +132,24 @@ fn vec_zeroizingvec_alias() {
     let mut v = ZeroizingVec::new();
     v.extend([1, 2, 3]);
 }
+
+#[test]
+fn vec_zeroizing_custom_index() {
+    fn check_zeroize<Z: zeroize::Zeroize + zeroize::ZeroizeOnDrop>(_z: &Z) {}
+
+    let mut v = ZeroizingVecIn::<usize, Global, u32>::new();
+    v.extend([1, 2, 3]);
+    check_zeroize(&v);
+}
+
+#[test]
+fn vec_zeroizing_custom_index_verify() {
+    let alloc = TestAlloc::new(Global);
+    let custom = Custom::<ZeroizingAlloc<&TestAlloc<Global>>, u32>::new(ZeroizingAlloc(&alloc));
+    let mut v = FlexVec::<usize, _>::new_in(custom);
+    v.extend([1, 2, 3]);
+    drop(v);
+    let log = alloc.released.borrow().clone();
+    assert_eq!(log.len(), 1);
+    assert!(log[0].iter().all(|i| *i == 0));
+}